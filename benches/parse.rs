@@ -0,0 +1,73 @@
+//! Compares the byte-level parser against the regex-per-call path it replaced.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use regex::Regex;
+use tin::{Atin, FormatError, Itin, ParseError, Ssn, Tin, parse_components};
+
+const INPUTS: [&str; 6] = [
+    "123-45-6789",
+    "123456789",
+    "900-70-1234",
+    "900-93-5678",
+    "12a-45-6789",
+    "123-456789",
+];
+
+/// The previous regex-based `parse_components`, compiled per call as it was.
+fn parse_components_regex(s: &str) -> Result<(u16, u8, u16), ParseError> {
+    let re = Regex::new(r"\A(?:(\d{3})-(\d{2})-(\d{4})|(\d{9}))\z").unwrap();
+    let caps = re
+        .captures(s)
+        .ok_or_else(|| ParseError::InvalidFormat(FormatError::at(s, 0)))?;
+    let (area, group, serial) =
+        if let (Some(a), Some(g), Some(s)) = (caps.get(1), caps.get(2), caps.get(3)) {
+            (a.as_str(), g.as_str(), s.as_str())
+        } else {
+            let full = caps.get(4).unwrap().as_str();
+            (&full[0..3], &full[3..5], &full[5..9])
+        };
+    Ok((
+        area.parse().unwrap(),
+        group.parse().unwrap(),
+        serial.parse().unwrap(),
+    ))
+}
+
+fn components(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_components");
+    group.bench_function("regex", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                let _ = black_box(parse_components_regex(black_box(input)));
+            }
+        })
+    });
+    group.bench_function("state_machine", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                let _ = black_box(parse_components(black_box(input)));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn types(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_str");
+    group.bench_function("Ssn", |b| {
+        b.iter(|| black_box("123-45-6789").parse::<Ssn>())
+    });
+    group.bench_function("Itin", |b| {
+        b.iter(|| black_box("900-70-1234").parse::<Itin>())
+    });
+    group.bench_function("Atin", |b| {
+        b.iter(|| black_box("900-93-5678").parse::<Atin>())
+    });
+    group.bench_function("Tin", |b| b.iter(|| black_box("900701234").parse::<Tin>()));
+    group.finish();
+}
+
+criterion_group!(benches, components, types);
+criterion_main!(benches);
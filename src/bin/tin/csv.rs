@@ -0,0 +1,236 @@
+//! `tin validate-csv`: validating a column of a CSV file.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+use tin::ErrorKind;
+
+use crate::{Kind, masked, validate};
+
+/// Columns appended to every row, in order.
+const RESULT_COLUMNS: [&str; 5] = [
+    "tin_valid",
+    "tin_kind",
+    "tin_normalized",
+    "tin_masked",
+    "tin_error",
+];
+
+#[derive(Debug, Args)]
+pub struct CsvArgs {
+    /// The CSV file to read. Reads stdin when omitted or `-`.
+    input: Option<PathBuf>,
+    /// The column to validate: a header name, or a 1-based position.
+    #[arg(long)]
+    column: String,
+    /// The kind to validate as.
+    #[arg(long, value_enum, default_value_t = Kind::Auto)]
+    kind: Kind,
+    /// The field delimiter.
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+    /// Treat the first row as data rather than column names.
+    #[arg(long)]
+    no_headers: bool,
+}
+
+/// Counts printed after the last row.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    rows: usize,
+    valid: usize,
+    errors: BTreeMap<ErrorKind, usize>,
+}
+
+impl Summary {
+    fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "rows:    {}", self.rows)?;
+        writeln!(out, "valid:   {}", self.valid)?;
+        writeln!(out, "invalid: {}", self.rows - self.valid)?;
+        for (kind, count) in &self.errors {
+            writeln!(out, "  {kind}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates the column, writing the rows with result columns appended to
+/// `out` and the summary to stderr. Returns `true` if every value is valid.
+pub fn run(out: &mut impl Write, args: &CsvArgs) -> io::Result<bool> {
+    let input: Box<dyn Read> = match &args.input {
+        Some(path) if path.as_os_str() != "-" => Box::new(File::open(path)?),
+        _ => Box::new(io::stdin().lock()),
+    };
+    let summary = validate_csv(input, out, args)?;
+    summary.write(&mut io::stderr().lock())?;
+    Ok(summary.rows == summary.valid)
+}
+
+fn validate_csv(input: impl Read, out: &mut impl Write, args: &CsvArgs) -> io::Result<Summary> {
+    let delimiter = u8::try_from(args.delimiter)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or_else(|| invalid_input("the delimiter must be an ASCII character"))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(!args.no_headers)
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(input);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(out);
+
+    let headers = if args.no_headers {
+        None
+    } else {
+        Some(reader.headers()?.clone())
+    };
+    let column = match &headers {
+        None => column_position(&args.column),
+        Some(headers) => headers
+            .iter()
+            .position(|h| h == args.column)
+            .or_else(|| {
+                headers
+                    .iter()
+                    .position(|h| h.eq_ignore_ascii_case(&args.column))
+            })
+            .or_else(|| column_position(&args.column)),
+    }
+    .ok_or_else(|| invalid_input(&format!("no column named `{}`", args.column)))?;
+    // Short rows are padded so the result columns stay aligned.
+    let width = headers
+        .as_ref()
+        .map_or(0, csv::StringRecord::len)
+        .max(column + 1);
+    if let Some(mut headers) = headers {
+        headers.extend(RESULT_COLUMNS);
+        writer.write_record(&headers)?;
+    }
+
+    let mut summary = Summary::default();
+    let mut record = csv::StringRecord::new();
+    while reader.read_record(&mut record)? {
+        while record.len() < width {
+            record.push_field("");
+        }
+        let input = record[column].trim();
+        let result = validate(input, args.kind);
+        summary.rows += 1;
+        let masked = masked(input, &result);
+        match &result {
+            Ok((kind, normalized)) => {
+                summary.valid += 1;
+                record.extend(["true", &kind.to_string(), normalized, &masked, ""]);
+            }
+            Err(err) => {
+                *summary.errors.entry(err.kind()).or_default() += 1;
+                record.extend(["false", "", "", &masked, &err.to_string()]);
+            }
+        }
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(summary)
+}
+
+/// Parses a 1-based column position.
+fn column_position(column: &str) -> Option<usize> {
+    column.parse::<usize>().ok()?.checked_sub(1)
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPLOYEES: &str = "\
+name,SSN,dept
+Ada,123456789,eng
+Bob,000-12-3456,ops
+Cy
+Di,900-70-1234,ops
+";
+
+    fn args(column: &str) -> CsvArgs {
+        CsvArgs {
+            input: None,
+            column: column.to_owned(),
+            kind: Kind::Auto,
+            delimiter: ',',
+            no_headers: false,
+        }
+    }
+
+    fn run_csv(input: &str, args: &CsvArgs) -> io::Result<(String, Summary)> {
+        let mut out = Vec::new();
+        let summary = validate_csv(input.as_bytes(), &mut out, args)?;
+        Ok((String::from_utf8(out).unwrap(), summary))
+    }
+
+    #[test]
+    fn appends_result_columns() {
+        let (out, _) = run_csv(EMPLOYEES, &args("ssn")).unwrap();
+        assert_eq!(
+            out,
+            "\
+name,SSN,dept,tin_valid,tin_kind,tin_normalized,tin_masked,tin_error
+Ada,123456789,eng,true,SSN,123-45-6789,XXX-XX-6789,
+Bob,000-12-3456,ops,false,,,XXX-XX-3456,invalid area number: 0
+Cy,,,false,,,,invalid format: unexpected end of input at byte 0
+Di,900-70-1234,ops,true,ITIN,900-70-1234,XXX-XX-1234,
+"
+        );
+    }
+
+    #[test]
+    fn summarizes_by_error_kind() {
+        let (_, summary) = run_csv(EMPLOYEES, &args("SSN")).unwrap();
+        assert_eq!(summary.rows, 4);
+        assert_eq!(summary.valid, 2);
+        assert_eq!(
+            summary.errors,
+            BTreeMap::from([(ErrorKind::InvalidFormat, 1), (ErrorKind::InvalidArea, 1)])
+        );
+
+        let mut footer = Vec::new();
+        summary.write(&mut footer).unwrap();
+        assert_eq!(
+            String::from_utf8(footer).unwrap(),
+            "rows:    4\nvalid:   2\ninvalid: 2\n  invalid format: 1\n  invalid area number: 1\n"
+        );
+    }
+
+    #[test]
+    fn selects_by_position_and_kind() {
+        let mut args = args("2");
+        args.kind = Kind::Itin;
+        let (_, summary) = run_csv(EMPLOYEES, &args).unwrap();
+        assert_eq!(summary.valid, 1);
+    }
+
+    #[test]
+    fn headerless_input() {
+        let mut args = args("1");
+        args.no_headers = true;
+        args.delimiter = ';';
+        let (out, _) = run_csv("12-3456789;x\n", &args).unwrap();
+        assert_eq!(out, "12-3456789;x;true;EIN;12-3456789;XX-XXX6789;\n");
+    }
+
+    #[test]
+    fn unknown_column() {
+        let mut out = Vec::new();
+        let err = validate_csv(EMPLOYEES.as_bytes(), &mut out, &args("tin")).unwrap_err();
+        assert!(out.is_empty());
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "no column named `tin`");
+    }
+}
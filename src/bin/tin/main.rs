@@ -0,0 +1,355 @@
+//! The `tin` command-line tool.
+//!
+//! Exit codes: 0 when every input is valid (or, for `scan`, nothing was
+//! found), 1 when any input is invalid (or something was found), and 2 for
+//! usage or I/O errors.
+
+mod csv;
+mod scan;
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use tin::{Atin, Ein, Itin, Layout, ParseError, Ssn, Tin, TinKind};
+
+const EXIT_INVALID: u8 = 1;
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "tin",
+    version,
+    about = "Validate U.S. Taxpayer Identification Numbers"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Validate numbers given as arguments, or one per line on stdin.
+    Validate {
+        /// Numbers to validate. Reads stdin when omitted.
+        values: Vec<String>,
+        /// The kind to validate as.
+        #[arg(long, value_enum, default_value_t = Kind::Auto)]
+        kind: Kind,
+        /// The output format.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Show how a number fares against the rules of every kind.
+    Explain {
+        /// The number to explain.
+        value: String,
+        /// The output format.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Find TINs in files, printing only masked values.
+    Scan(scan::ScanArgs),
+    /// Validate a column of a CSV file, appending result columns to each row.
+    ValidateCsv(csv::CsvArgs),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Kind {
+    /// Detect SSN, ITIN, ATIN, or EIN from the number itself.
+    Auto,
+    Ssn,
+    Itin,
+    Atin,
+    Ein,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut out = io::stdout().lock();
+    let result = match cli.command {
+        Command::Validate {
+            values,
+            kind,
+            format,
+        } => run_validate(&mut out, values, kind, format),
+        Command::Explain { value, format } => run_explain(&mut out, &value, format),
+        Command::Scan(args) => scan::run(&mut out, &args),
+        Command::ValidateCsv(args) => csv::run(&mut out, &args),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_INVALID),
+        Err(err) => {
+            eprintln!("tin: {err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Validates every value, returning `true` if all are valid.
+fn run_validate(
+    out: &mut impl Write,
+    values: Vec<String>,
+    kind: Kind,
+    format: Format,
+) -> io::Result<bool> {
+    let mut all_valid = true;
+    let mut check = |input: &str| -> io::Result<()> {
+        let input = input.trim();
+        let result = validate(input, kind);
+        all_valid &= result.is_ok();
+        write_result(out, input, &result, format)
+    };
+    if values.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                check(&line)?;
+            }
+        }
+    } else {
+        for value in &values {
+            check(value)?;
+        }
+    }
+    Ok(all_valid)
+}
+
+/// Evaluates every rule of every kind, returning `true` if the input is a
+/// valid [`Tin`].
+fn run_explain(out: &mut impl Write, input: &str, format: Format) -> io::Result<bool> {
+    let input = input.trim();
+    let result = validate(input, Kind::Auto);
+    let trace = tin::explain(input);
+    let layout = match Layout::of(input) {
+        Some(Layout::Ssn) => "XXX-XX-XXXX",
+        Some(Layout::Ein) => "XX-XXXXXXX",
+        Some(Layout::Undashed) => "XXXXXXXXX",
+        None => "unrecognized",
+    };
+
+    match format {
+        Format::Text => {
+            writeln!(out, "input   {}", masked(input, &result))?;
+            writeln!(out, "layout  {layout}")?;
+            for check in trace.checks() {
+                let outcome = if check.passed() { "pass" } else { "fail" };
+                writeln!(
+                    out,
+                    "{:<7} {:<7} {outcome}  {}",
+                    check.kind().to_string(),
+                    check.rule().to_string(),
+                    check.reason()
+                )?;
+            }
+            match &result {
+                Ok((kind, _)) => writeln!(out, "result  {kind}")?,
+                Err(err) => writeln!(out, "result  invalid: {err}")?,
+            }
+        }
+        Format::Json => {
+            let checks: Vec<_> = trace
+                .checks()
+                .iter()
+                .map(|check| {
+                    json!({
+                        "kind": check.kind().to_string(),
+                        "rule": check.rule().to_string(),
+                        "passed": check.passed(),
+                        "reason": check.reason(),
+                    })
+                })
+                .collect();
+            let value = json!({
+                "masked": masked(input, &result),
+                "layout": layout,
+                "checks": checks,
+                "result": result_json(&result),
+            });
+            writeln!(out, "{value}")?;
+        }
+    }
+    Ok(result.is_ok())
+}
+
+/// Validates `input` as the given kind, returning the detected kind and the
+/// normalized form.
+fn validate(input: &str, kind: Kind) -> Result<(TinKind, String), ParseError> {
+    Ok(match kind {
+        Kind::Auto => {
+            let tin: Tin = input.parse()?;
+            (tin.kind(), tin.to_string())
+        }
+        Kind::Ssn => (TinKind::Ssn, input.parse::<Ssn>()?.to_string()),
+        Kind::Itin => (TinKind::Itin, input.parse::<Itin>()?.to_string()),
+        Kind::Atin => (TinKind::Atin, input.parse::<Atin>()?.to_string()),
+        Kind::Ein => (TinKind::Ein, input.parse::<Ein>()?.to_string()),
+    })
+}
+
+fn write_result(
+    out: &mut impl Write,
+    input: &str,
+    result: &Result<(TinKind, String), ParseError>,
+    format: Format,
+) -> io::Result<()> {
+    let masked = masked(input, result);
+    match format {
+        Format::Text => match result {
+            Ok((kind, _)) => writeln!(out, "{masked}\tvalid\t{kind}"),
+            Err(err) => writeln!(out, "{masked}\tinvalid\t{err}"),
+        },
+        Format::Json => {
+            let mut value = result_json(result);
+            value["masked"] = json!(masked);
+            writeln!(out, "{value}")
+        }
+    }
+}
+
+fn result_json(result: &Result<(TinKind, String), ParseError>) -> serde_json::Value {
+    match result {
+        Ok((kind, _)) => json!({ "valid": true, "kind": kind.to_string() }),
+        Err(err) => json!({
+            "valid": false,
+            "error": format!("{:?}", err.kind()),
+            "reason": err.to_string(),
+        }),
+    }
+}
+
+/// Returns the value to print in place of the input: the normalized form
+/// with all but the last 4 digits masked when valid, or the input with every
+/// digit masked, except the last 4 of a number with at least 9, when not.
+fn masked(input: &str, result: &Result<(TinKind, String), ParseError>) -> String {
+    let shown = match result {
+        Ok((_, normalized)) => normalized.as_str(),
+        Err(_) => input,
+    };
+    let digits = shown.bytes().filter(u8::is_ascii_digit).count();
+    let hidden = if digits >= 9 { digits - 4 } else { digits };
+    let mut seen = 0;
+    shown
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+            seen += 1;
+            if seen > hidden { c } else { 'X' }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_output(values: &[&str], kind: Kind, format: Format) -> (String, bool) {
+        let mut out = Vec::new();
+        let values = values.iter().map(|&v| v.to_owned()).collect();
+        let ok = run_validate(&mut out, values, kind, format).unwrap();
+        (String::from_utf8(out).unwrap(), ok)
+    }
+
+    #[test]
+    fn validates_with_auto_detection() {
+        let (out, ok) = validate_output(&["123456789", " 12-3456789 "], Kind::Auto, Format::Text);
+        assert!(ok);
+        assert_eq!(out, "XXX-XX-6789\tvalid\tSSN\nXX-XXX6789\tvalid\tEIN\n");
+    }
+
+    #[test]
+    fn reports_invalid_values_without_raw_digits() {
+        let (out, ok) = validate_output(&["900-70-1234", "12a"], Kind::Ssn, Format::Text);
+        assert!(!ok);
+        assert_eq!(
+            out,
+            "XXX-XX-1234\tinvalid\tinvalid area number: 900\n\
+             XXa\tinvalid\tinvalid format: unexpected letter at byte 2 of 3\n"
+        );
+    }
+
+    #[test]
+    fn json_output() {
+        let (out, _) = validate_output(&["900-70-1234", "000-12-3456"], Kind::Auto, Format::Json);
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            json!({ "masked": "XXX-XX-1234", "valid": true, "kind": "ITIN" })
+        );
+        assert_eq!(lines[1]["error"], "InvalidArea");
+        assert_eq!(lines[1]["masked"], "XXX-XX-3456");
+    }
+
+    #[test]
+    fn explain_checks_every_rule() {
+        let mut out = Vec::new();
+        assert!(run_explain(&mut out, "900701234", Format::Text).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "input   XXX-XX-1234\n\
+             layout  XXXXXXXXX\n\
+             SSN     format  pass  matches XXX-XX-XXXX or XXXXXXXXX\n\
+             SSN     area    fail  area 900 excludes SSN\n\
+             SSN     group   pass  group is 01–99\n\
+             SSN     serial  pass  serial is 0001–9999\n\
+             ITIN    format  pass  matches XXX-XX-XXXX or XXXXXXXXX\n\
+             ITIN    area    pass  area is 900–999\n\
+             ITIN    group   pass  group is in ITIN ranges\n\
+             ITIN    serial  pass  any serial is allowed\n\
+             ATIN    format  pass  matches XXX-XX-XXXX or XXXXXXXXX\n\
+             ATIN    area    pass  area is 900–999\n\
+             ATIN    group   fail  group 70 != 93 so not ATIN\n\
+             ATIN    serial  pass  any serial is allowed\n\
+             EIN     format  pass  matches XX-XXXXXXX or XXXXXXXXX\n\
+             EIN     prefix  pass  prefix is assigned by an IRS campus\n\
+             result  ITIN\n"
+        );
+    }
+
+    #[test]
+    fn explain_json() {
+        let mut out = Vec::new();
+        assert!(!run_explain(&mut out, "000-12-3456", Format::Json).unwrap());
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value["checks"][1],
+            json!({
+                "kind": "SSN",
+                "rule": "area",
+                "passed": false,
+                "reason": "area 000 is never issued",
+            })
+        );
+        assert_eq!(value["result"]["valid"], false);
+        assert!(!String::from_utf8(out).unwrap().contains("12-3456"));
+    }
+
+    #[test]
+    fn masks_short_inputs_entirely() {
+        let err = Err(ParseError::InvalidChecksum);
+        assert_eq!(masked("1234", &err), "XXXX");
+        assert_eq!(masked("123-45-678", &err), "XXX-XX-XXX");
+        assert_eq!(masked("123-45-67890", &err), "XXX-XX-X7890");
+    }
+
+    #[test]
+    fn cli_parses() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+}
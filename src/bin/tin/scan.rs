@@ -0,0 +1,588 @@
+//! `tin scan`: finding TINs in files.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use serde_json::json;
+use tin::TinKind;
+use tin::scan::Match;
+
+/// Bytes inspected for a NUL when deciding whether a file is binary.
+const BINARY_SNIFF: usize = 8 * 1024;
+
+/// Bytes read from a file at a time.
+const CHUNK: usize = 64 * 1024;
+
+/// Bytes kept on each side of a match: enough for the scanner's word
+/// boundaries and the confidence keyword windows.
+const CONTEXT: usize = 64;
+
+/// Bytes from its start that decide whether `XXX-XX-XXXX` matches, including
+/// the word boundary after it.
+const DECIDED: usize = 13;
+
+#[derive(Debug, Args)]
+pub struct ScanArgs {
+    /// Files or directories to scan.
+    #[arg(default_value = ".")]
+    paths: Vec<PathBuf>,
+    /// The output format.
+    #[arg(long, value_enum, default_value_t = ScanFormat::Text)]
+    format: ScanFormat,
+    /// Only report numbers that validate as an SSN, ITIN, or ATIN.
+    #[arg(long)]
+    validated_only: bool,
+    /// Only report numbers whose confidence score is at least this value.
+    #[arg(long, default_value_t = 0.0)]
+    min_confidence: f32,
+    /// Scan hidden files and directories.
+    #[arg(long)]
+    hidden: bool,
+    /// Scan files excluded by .gitignore, .ignore, and similar files.
+    #[arg(long)]
+    no_ignore: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ScanFormat {
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// A SARIF 2.1.0 log.
+    Sarif,
+}
+
+/// A reportable match, without the matched digits.
+#[derive(Debug, Clone, PartialEq)]
+struct Finding {
+    path: PathBuf,
+    line: usize,
+    column: usize,
+    end_column: usize,
+    kind: TinKind,
+    valid: bool,
+    confidence: f32,
+    masked: String,
+}
+
+/// Scans every file under the paths, returning `true` if nothing was found.
+///
+/// Files that cannot be read are reported on stderr and the rest are still
+/// scanned, but the scan then fails so that it exits with 2.
+pub fn run(out: &mut impl Write, args: &ScanArgs) -> io::Result<bool> {
+    let mut walk = ignore::WalkBuilder::new(&args.paths[0]);
+    for path in &args.paths[1..] {
+        walk.add(path);
+    }
+    walk.hidden(!args.hidden)
+        .standard_filters(!args.no_ignore)
+        .require_git(false)
+        .sort_by_file_name(|a, b| a.cmp(b));
+
+    let mut findings = Vec::new();
+    let mut failed = 0;
+    for entry in walk.build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                eprintln!("tin: {err}");
+                failed += 1;
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let file_findings =
+            match File::open(entry.path()).and_then(|file| scan_reader(entry.path(), file)) {
+                Ok(Some(file_findings)) => file_findings,
+                // Binary.
+                Ok(None) => continue,
+                Err(err) => {
+                    eprintln!("tin: {}: {err}", entry.path().display());
+                    failed += 1;
+                    continue;
+                }
+            };
+        for finding in file_findings {
+            if (finding.valid || !args.validated_only) && finding.confidence >= args.min_confidence
+            {
+                if args.format != ScanFormat::Sarif {
+                    write_finding(out, &finding, args.format)?;
+                }
+                findings.push(finding);
+            }
+        }
+    }
+    if args.format == ScanFormat::Sarif {
+        let base = std::env::current_dir().ok();
+        writeln!(out, "{:#}", sarif(&findings, base.as_deref()))?;
+    }
+    match failed {
+        0 => Ok(findings.is_empty()),
+        1 => Err(io::Error::other("1 input could not be scanned")),
+        n => Err(io::Error::other(format!("{n} inputs could not be scanned"))),
+    }
+}
+
+/// Finds every match in `reader`, or returns `None` if it starts with
+/// binary data.
+///
+/// Reads a piece at a time, keeping only the bytes that a match not yet
+/// reported could still need.
+fn scan_reader(path: &Path, mut reader: impl Read) -> io::Result<Option<Vec<Finding>>> {
+    let mut buf = Vec::with_capacity(CHUNK + 2 * CONTEXT);
+    reader
+        .by_ref()
+        .take(BINARY_SNIFF as u64)
+        .read_to_end(&mut buf)?;
+    if buf.contains(&0) {
+        return Ok(None);
+    }
+    let mut eof = buf.len() < BINARY_SNIFF;
+    let mut scanner = Scanner::new(path);
+    let mut findings = Vec::new();
+    loop {
+        scanner.scan(&mut buf, eof, &mut findings);
+        if eof {
+            return Ok(Some(findings));
+        }
+        eof = reader.by_ref().take(CHUNK as u64).read_to_end(&mut buf)? == 0;
+    }
+}
+
+/// Scan state carried from one piece of a file to the next, with 1-based
+/// line and column numbers counted in Unicode code points.
+struct Scanner<'p> {
+    path: &'p Path,
+    /// Where in the buffer scanning resumes.
+    pos: usize,
+    /// How far into the buffer `line` and `column` have been counted.
+    counted: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'p> Scanner<'p> {
+    fn new(path: &'p Path) -> Self {
+        Self {
+            path,
+            pos: 0,
+            counted: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Reports the matches in `buf` that more input cannot change, then drops
+    /// the bytes no later match needs.
+    fn scan(&mut self, buf: &mut Vec<u8>, eof: bool, findings: &mut Vec<Finding>) {
+        let mut stop = if eof {
+            buf.len()
+        } else {
+            buf.len().saturating_sub(DECIDED)
+        };
+        let mut end = self.pos;
+        for m in tin::scan::find_iter_at(buf.as_slice(), self.pos) {
+            if !eof && m.end() + CONTEXT > buf.len() {
+                stop = stop.min(m.start());
+                break;
+            }
+            self.count_to(buf, m.start());
+            findings.push(Finding {
+                path: self.path.to_owned(),
+                line: self.line,
+                column: self.column,
+                end_column: self.column + m.range().len(),
+                kind: m.kind(),
+                valid: m.is_valid(),
+                confidence: m.confidence(buf.as_slice()).value(),
+                masked: masked(buf, &m),
+            });
+            end = m.end();
+        }
+        self.pos = stop.max(end);
+
+        let consumed = self.pos.saturating_sub(CONTEXT);
+        self.count_to(buf, consumed);
+        buf.drain(..consumed);
+        self.pos -= consumed;
+        self.counted -= consumed;
+    }
+
+    /// Advances the line and column to `to`.
+    fn count_to(&mut self, buf: &[u8], to: usize) {
+        if to <= self.counted {
+            return;
+        }
+        for &b in &buf[self.counted..to] {
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if b & 0xC0 != 0x80 {
+                self.column += 1;
+            }
+        }
+        self.counted = to;
+    }
+}
+
+/// Returns `XXX-XX-` followed by the last 4 digits of the match.
+fn masked(bytes: &[u8], m: &Match) -> String {
+    let matched = &bytes[m.range()];
+    let last_four = String::from_utf8_lossy(&matched[matched.len() - 4..]);
+    format!("XXX-XX-{last_four}")
+}
+
+fn write_finding(out: &mut impl Write, finding: &Finding, format: ScanFormat) -> io::Result<()> {
+    let Finding {
+        path,
+        line,
+        column,
+        kind,
+        valid,
+        confidence,
+        masked,
+        ..
+    } = finding;
+    let path = path.display().to_string().replace('\\', "/");
+    match format {
+        ScanFormat::Text => {
+            let validity = if *valid { "valid" } else { "invalid" };
+            writeln!(
+                out,
+                "{path}:{line}:{column}: {kind} {masked} ({validity}, confidence {confidence:.2})"
+            )
+        }
+        ScanFormat::Json => {
+            let value = json!({
+                "path": path,
+                "line": line,
+                "column": column,
+                "kind": kind.to_string(),
+                "valid": valid,
+                "confidence": confidence,
+                "masked": masked,
+            });
+            writeln!(out, "{value}")
+        }
+        ScanFormat::Sarif => unreachable!("SARIF is written as a single log"),
+    }
+}
+
+fn rule_id(kind: TinKind) -> String {
+    format!("tin/{}", kind.to_string().to_lowercase())
+}
+
+/// The SARIF base that relative artifact URIs resolve against.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Builds a SARIF 2.1.0 log with one rule per kind, with paths under `base`
+/// given relative to it.
+fn sarif(findings: &[Finding], base: Option<&Path>) -> serde_json::Value {
+    let rules: Vec<_> = [TinKind::Ssn, TinKind::Itin, TinKind::Atin]
+        .into_iter()
+        .map(|kind| {
+            json!({
+                "id": rule_id(kind),
+                "name": format!("{kind}Detected"),
+                "shortDescription": { "text": format!("Possible {kind} in plain text") },
+            })
+        })
+        .collect();
+    let results: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let validity = if f.valid {
+                "valid"
+            } else {
+                "TIN-shaped but invalid"
+            };
+            json!({
+                "ruleId": rule_id(f.kind),
+                "level": if f.valid { "error" } else { "warning" },
+                "message": { "text": format!("{} {} ({validity})", f.kind, f.masked) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": artifact_location(&f.path, base),
+                        "region": {
+                            "startLine": f.line,
+                            "startColumn": f.column,
+                            "endColumn": f.end_column,
+                        },
+                    },
+                }],
+                "properties": { "confidence": f.confidence },
+            })
+        })
+        .collect();
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "tin",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                "rules": rules,
+            },
+        },
+        "columnKind": "unicodeCodePoints",
+        "results": results,
+    });
+    if let Some(base) = base {
+        run["originalUriBaseIds"] = json!({
+            SRCROOT: { "uri": format!("file://{}/", uri_path(base)) },
+        });
+    }
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [run],
+    })
+}
+
+/// Returns a SARIF artifact location: a URI reference relative to `base`
+/// when `path` is relative or under it, otherwise a `file` URI.
+fn artifact_location(path: &Path, base: Option<&Path>) -> serde_json::Value {
+    let relative = match base {
+        _ if path.is_relative() => Some(path),
+        Some(base) => path.strip_prefix(base).ok(),
+        None => None,
+    };
+    match relative {
+        Some(relative) => json!({ "uri": uri_path(relative), "uriBaseId": SRCROOT }),
+        None => json!({ "uri": format!("file://{}", uri_path(path)) }),
+    }
+}
+
+/// Joins the components of `path` with `/`, percent-encoding each name.
+fn uri_path(path: &Path) -> String {
+    let mut segments = Vec::new();
+    for component in path.components() {
+        segments.push(match component {
+            // A Windows drive, as in `file:///C:/...`.
+            Component::Prefix(prefix) => format!("/{}", prefix.as_os_str().to_string_lossy()),
+            Component::RootDir if segments.is_empty() => String::new(),
+            Component::RootDir | Component::CurDir => continue,
+            Component::ParentDir => "..".to_owned(),
+            Component::Normal(name) => percent_encode(name.as_encoded_bytes()),
+        });
+    }
+    segments.join("/")
+}
+
+/// Percent-encodes every byte but the URI unreserved characters.
+fn percent_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    for &b in bytes {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// A scratch directory removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tin-scan-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: &[u8]) {
+            let path = self.0.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn args(paths: Vec<PathBuf>, format: ScanFormat) -> ScanArgs {
+        ScanArgs {
+            paths,
+            format,
+            validated_only: false,
+            min_confidence: 0.0,
+            hidden: false,
+            no_ignore: false,
+        }
+    }
+
+    fn run_to_string(args: &ScanArgs) -> (String, bool) {
+        let mut out = Vec::new();
+        let clean = run(&mut out, args).unwrap();
+        (String::from_utf8(out).unwrap(), clean)
+    }
+
+    fn scan_bytes(bytes: &[u8]) -> Vec<Finding> {
+        scan_reader(Path::new("a.txt"), bytes).unwrap().unwrap()
+    }
+
+    #[test]
+    fn reports_lines_and_columns() {
+        let findings = scan_bytes("id 1\nnée 123-45-6789 and 900701234\n".as_bytes());
+        let positions: Vec<_> = findings
+            .iter()
+            .map(|f| (f.line, f.column, f.end_column))
+            .collect();
+        assert_eq!(positions, [(2, 5, 16), (2, 21, 30)]);
+        assert_eq!(findings[1].masked, "XXX-XX-1234");
+        assert_eq!(findings[1].kind, TinKind::Itin);
+    }
+
+    #[test]
+    fn streams_large_files() {
+        // Matches, with context, straddling every chunk boundary.
+        let mut text = String::new();
+        let mut expected = Vec::new();
+        let mut line = 1;
+        while text.len() < 3 * CHUNK + BINARY_SNIFF {
+            let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+            let column = text[line_start..].chars().count() + 5;
+            expected.push((line, column));
+            text.push_str("ssn 234-56-7890 é ");
+            if expected.len() % 7 == 0 {
+                text.push('\n');
+                line += 1;
+            }
+        }
+        let findings = scan_bytes(text.as_bytes());
+        assert_eq!(findings.len(), expected.len());
+        for (finding, &(line, column)) in findings.iter().zip(&expected) {
+            assert_eq!((finding.line, finding.column), (line, column));
+            assert_eq!(finding.confidence, findings[0].confidence);
+        }
+    }
+
+    #[test]
+    fn binary_is_sniffed_from_the_start() {
+        assert_eq!(
+            scan_reader(Path::new("a"), &b"a\x00 123-45-6789"[..]).unwrap(),
+            None
+        );
+        let mut late_nul = vec![b' '; BINARY_SNIFF];
+        late_nul.extend_from_slice(b"\x00 123-45-6789");
+        assert_eq!(scan_bytes(&late_nul).len(), 1);
+    }
+
+    #[test]
+    fn unreadable_inputs_fail_the_scan() {
+        let dir = TempDir::new("unreadable");
+        dir.write("a.txt", b"ssn 234-56-7890\n");
+        let args = args(
+            vec![dir.0.join("a.txt"), dir.0.join("missing")],
+            ScanFormat::Text,
+        );
+        let mut out = Vec::new();
+        let err = run(&mut out, &args).unwrap_err();
+        assert_eq!(err.to_string(), "1 input could not be scanned");
+        assert!(String::from_utf8(out).unwrap().contains("XXX-XX-7890"));
+    }
+
+    #[test]
+    fn sarif_uris_are_relative_and_encoded() {
+        let base = Path::new("/work/repo");
+        assert_eq!(
+            artifact_location(Path::new("/work/repo/docs/a b#é.txt"), Some(base)),
+            json!({ "uri": "docs/a%20b%23%C3%A9.txt", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            artifact_location(Path::new("./src/../x.txt"), Some(base)),
+            json!({ "uri": "src/../x.txt", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            artifact_location(Path::new("/tmp/100%.txt"), Some(base)),
+            json!({ "uri": "file:///tmp/100%25.txt" })
+        );
+    }
+
+    #[test]
+    fn walks_directories_honoring_ignores_and_skipping_binaries() {
+        let dir = TempDir::new("walk");
+        dir.write("a.txt", b"ssn 234-56-7890\n");
+        dir.write("sub/b.log", b"x\n900-70-1234\n");
+        dir.write("image.bin", b"\x00\x01 123-45-6789");
+        dir.write("ignored/c.txt", b"123-45-6789");
+        dir.write(".ignore", b"ignored/\n");
+        dir.write(".hidden", b"123-45-6789");
+
+        let (out, clean) = run_to_string(&args(vec![dir.0.clone()], ScanFormat::Text));
+        assert!(!clean);
+        let root = dir.0.display().to_string();
+        assert_eq!(
+            out.replace(&root, "ROOT"),
+            "ROOT/a.txt:1:5: SSN XXX-XX-7890 (valid, confidence 0.99)\n\
+             ROOT/sub/b.log:2:1: ITIN XXX-XX-1234 (valid, confidence 0.86)\n"
+        );
+    }
+
+    #[test]
+    fn filters() {
+        let dir = TempDir::new("filters");
+        dir.write("a.txt", b"000-12-3456 order 234567890 SSN 234-56-7890");
+        let mut args = args(vec![dir.0.clone()], ScanFormat::Json);
+        args.validated_only = true;
+        args.min_confidence = 0.5;
+        let (out, _) = run_to_string(&args);
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["masked"], "XXX-XX-7890");
+        assert_eq!(lines[0]["column"], 33);
+    }
+
+    #[test]
+    fn clean_directory() {
+        let dir = TempDir::new("clean");
+        dir.write("a.txt", b"nothing here");
+        assert_eq!(
+            run_to_string(&args(vec![dir.0.clone()], ScanFormat::Text)),
+            (String::new(), true)
+        );
+    }
+
+    #[test]
+    fn sarif_log() {
+        let dir = TempDir::new("sarif");
+        dir.write("a.txt", b"\n  000-12-3456");
+        let (out, clean) = run_to_string(&args(vec![dir.0.join("a.txt")], ScanFormat::Sarif));
+        assert!(!clean);
+        let log: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "tin/ssn");
+        assert_eq!(result["level"], "warning");
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 3);
+        // The temporary directory is outside the working directory.
+        let uri = result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap();
+        assert!(
+            uri.starts_with("file:///") && uri.ends_with("/a.txt"),
+            "{uri}"
+        );
+        assert!(!out.contains("000-12"));
+    }
+}
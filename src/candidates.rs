@@ -0,0 +1,241 @@
+//! Ambiguity-aware parsing of nine-digit inputs.
+//!
+//! A bare `123456789` is a valid SSN and a valid EIN. [`Tin::from_str`] picks
+//! the SSN reading; [`Candidates`] keeps every valid reading instead and lets
+//! the caller resolve them with what it knows about the payee.
+
+use core::str::FromStr;
+
+use crate::{Ein, FormatError, ParseError, Tin, parse};
+
+/// The punctuation layout of a nine-digit input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// `XXX-XX-XXXX`, implying an SSN, ITIN, or ATIN.
+    Ssn,
+    /// `XX-XXXXXXX`, implying an EIN.
+    Ein,
+    /// `XXXXXXXXX`, implying nothing.
+    Undashed,
+}
+
+impl Layout {
+    /// Returns the layout of the input, or `None` if it is not a nine-digit
+    /// number in any supported layout.
+    pub fn of(s: &str) -> Option<Self> {
+        if parse::digits(s, parse::SSN_BREAKS).is_ok() {
+            Some(if s.len() == parse::DIGITS {
+                Layout::Undashed
+            } else {
+                Layout::Ssn
+            })
+        } else if parse::digits(s, parse::EIN_BREAKS).is_ok() {
+            Some(Layout::Ein)
+        } else {
+            None
+        }
+    }
+}
+
+/// Caller knowledge used to choose between readings of an ambiguous input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// The payee is a person.
+    Individual,
+    /// The payee is a business or other entity.
+    Business,
+    /// Form W-2, which reports an employee's TIN.
+    FormW2,
+    /// Form W-4, completed by an employee.
+    FormW4,
+    /// Form SS-4, the application for an EIN.
+    FormSs4,
+    /// Form W-9, which may carry either an individual's or an entity's TIN.
+    FormW9,
+    /// The 1099 series, whose recipient may be an individual or an entity.
+    Form1099,
+}
+
+impl Context {
+    /// Returns `true` if a TIN of this kind can appear in this context.
+    fn admits(self, tin: &Tin) -> bool {
+        let is_ein = matches!(tin, Tin::Ein(_));
+        match self {
+            Context::Individual | Context::FormW2 | Context::FormW4 => !is_ein,
+            Context::Business | Context::FormSs4 => is_ein,
+            Context::FormW9 | Context::Form1099 => true,
+        }
+    }
+}
+
+/// Every valid reading of a nine-digit input, most likely first.
+///
+/// Dashed input has a single reading, the one its layout implies. Undashed
+/// input may be both an SSN-family number and an EIN; the SSN-family reading
+/// ranks first, matching [`Tin::from_str`].
+///
+/// # Example
+///
+/// ```
+/// use tin::{Candidates, Context, Tin};
+///
+/// let candidates: Candidates = "123456789".parse().unwrap();
+/// assert!(candidates.is_ambiguous());
+/// assert!(matches!(candidates.best(), Tin::Ssn(_)));
+/// assert!(matches!(candidates.resolve(Context::Business), Some(Tin::Ein(_))));
+/// assert_eq!(candidates.resolve(Context::FormW9), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Candidates {
+    layout: Layout,
+    best: Tin,
+    other: Option<Tin>,
+}
+
+impl Candidates {
+    /// Returns the punctuation layout of the input.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the highest-ranked reading.
+    pub fn best(&self) -> &Tin {
+        &self.best
+    }
+
+    /// Returns `true` if the input has more than one valid reading.
+    pub fn is_ambiguous(&self) -> bool {
+        self.other.is_some()
+    }
+
+    /// Returns the number of valid readings.
+    pub fn len(&self) -> usize {
+        1 + usize::from(self.other.is_some())
+    }
+
+    /// Always `false`; a candidate set holds at least one reading.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Iterates over the readings, most likely first.
+    pub fn iter(&self) -> impl Iterator<Item = &Tin> {
+        core::iter::once(&self.best).chain(self.other.as_ref())
+    }
+
+    /// Returns the only reading consistent with the context, or `None` if
+    /// the context admits none or more than one of them.
+    pub fn resolve(&self, context: Context) -> Option<&Tin> {
+        let mut admitted = self.iter().filter(|tin| context.admits(tin));
+        let tin = admitted.next()?;
+        admitted.next().is_none().then_some(tin)
+    }
+}
+
+impl FromStr for Candidates {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(layout) = Layout::of(s) else {
+            // Neither layout fits; report the one that matched more, as `Tin` does.
+            let ssn = parse::digits(s, parse::SSN_BREAKS).err().unwrap_or(0);
+            let ein = parse::digits(s, parse::EIN_BREAKS).err().unwrap_or(0);
+            return Err(ParseError::InvalidFormat(FormatError::at(s, ssn.max(ein))));
+        };
+        let (best, other) = match layout {
+            Layout::Ssn => (s.parse()?, None),
+            Layout::Ein => (Tin::Ein(s.parse()?), None),
+            Layout::Undashed => match (s.parse::<Tin>(), s.parse::<Ein>()) {
+                (Ok(tin), Ok(ein)) => (tin, Some(Tin::Ein(ein))),
+                (Ok(tin), Err(_)) => (tin, None),
+                (Err(_), Ok(ein)) => (Tin::Ein(ein), None),
+                (Err(err), Err(_)) => return Err(err),
+            },
+        };
+        Ok(Self {
+            layout,
+            best,
+            other,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_detection() {
+        assert_eq!(Layout::of("123-45-6789"), Some(Layout::Ssn));
+        assert_eq!(Layout::of("12-3456789"), Some(Layout::Ein));
+        assert_eq!(Layout::of("123456789"), Some(Layout::Undashed));
+        assert_eq!(Layout::of("1234-56789"), None);
+    }
+
+    #[test]
+    fn dashed_inputs_have_one_reading() {
+        let c: Candidates = "123-45-6789".parse().unwrap();
+        assert_eq!(c.layout(), Layout::Ssn);
+        assert!(!c.is_ambiguous());
+        assert!(matches!(c.best(), Tin::Ssn(_)));
+
+        let c: Candidates = "12-3456789".parse().unwrap();
+        assert_eq!(c.layout(), Layout::Ein);
+        assert_eq!(c.len(), 1);
+        assert!(matches!(c.best(), Tin::Ein(_)));
+    }
+
+    #[test]
+    fn undashed_input_keeps_both_readings() {
+        let c: Candidates = "123456789".parse().unwrap();
+        let readings: Vec<_> = c.iter().collect();
+        assert!(matches!(readings[..], [Tin::Ssn(_), Tin::Ein(_)]));
+    }
+
+    #[test]
+    fn undashed_input_drops_invalid_readings() {
+        // Prefix 07 is never assigned, so only the SSN reading survives.
+        let c: Candidates = "071234567".parse().unwrap();
+        assert!(!c.is_ambiguous());
+        assert!(matches!(c.best(), Tin::Ssn(_)));
+
+        // Area 666 is never issued, so only the EIN reading survives.
+        let c: Candidates = "666123456".parse().unwrap();
+        assert!(matches!(c.best(), Tin::Ein(_)));
+    }
+
+    #[test]
+    fn no_valid_reading_reports_ssn_family_error() {
+        // Area 000 and prefix 00 are both invalid.
+        assert!(matches!(
+            "000123456".parse::<Candidates>(),
+            Err(ParseError::InvalidArea(0))
+        ));
+        assert!(matches!(
+            "12a456789".parse::<Candidates>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn resolve_by_context() {
+        let c: Candidates = "123456789".parse().unwrap();
+        assert!(matches!(c.resolve(Context::Individual), Some(Tin::Ssn(_))));
+        assert!(matches!(c.resolve(Context::FormW2), Some(Tin::Ssn(_))));
+        assert!(matches!(c.resolve(Context::FormSs4), Some(Tin::Ein(_))));
+        assert_eq!(c.resolve(Context::Form1099), None);
+
+        let c: Candidates = "12-3456789".parse().unwrap();
+        assert!(matches!(c.resolve(Context::FormW9), Some(Tin::Ein(_))));
+        assert_eq!(c.resolve(Context::Individual), None);
+    }
+
+    #[test]
+    fn debug_masks_readings() {
+        let c: Candidates = "123456789".parse().unwrap();
+        assert_eq!(
+            format!("{c:?}"),
+            "Candidates { layout: Undashed, best: Ssn(XXX-XX-6789), other: Some(Ein(XX-XXX6789)) }"
+        );
+    }
+}
@@ -0,0 +1,576 @@
+//! Confidence scoring for scanner matches.
+//!
+//! A nine-digit number is not necessarily a TIN. [`score`] weighs the
+//! evidence around a [`Match`]: nearby keywords such as "SSN" or "order",
+//! how the number is punctuated, whether it validates, and whether it is a
+//! well-known fake. The result is a [`Confidence`] between 0 and 1.
+//!
+//! The signals are combined by logistic regression, with weights fitted to
+//! the labelled corpus in `data/confidence_corpus.tsv`: 4,000 lines, each
+//! with one TIN-shaped number, of which 40% are TINs. The corpus is
+//! synthetic, built from templates modelled on HR exports, tax forms, logs,
+//! and order records; its header describes how. Three quarters of it is
+//! used for fitting and the rest is held out.
+//!
+//! On the held-out lines the expected calibration error, over ten
+//! equal-width score buckets, is 0.016: a score of 0.85 means about 85% of
+//! such matches in the corpus were TINs. The largest gap in a bucket of at
+//! least 100 lines is 0.06. Calibration only carries over to text that
+//! resembles the corpus, so check thresholds against a sample of your own
+//! data.
+//!
+//! | Evidence | Score |
+//! |----------|-------|
+//! | Valid, dashed, next to "SSN" | 0.99 |
+//! | Valid, dashed, no context | 0.86 |
+//! | Valid, undashed, next to "SSN" | 0.85 |
+//! | Valid, undashed, no context | 0.17 |
+//! | Valid, undashed, next to "order" | < 0.01 |
+//! | Shape only (fails validation) | ≤ 0.15 |
+//! | Known fake, such as 078-05-1120 | ≤ 0.02 |
+//!
+//! # Example
+//!
+//! ```
+//! let text = "SSN: 234-56-7890. Order 234567890 shipped.";
+//! let scores: Vec<f32> = tin::scan::find_iter(text)
+//!     .map(|m| m.confidence(text).value())
+//!     .collect();
+//! assert!(scores[0] > 0.9);
+//! assert!(scores[1] < 0.1);
+//! ```
+
+use crate::parse;
+use crate::scan::Match;
+
+/// Bytes before a match searched for keywords. The window also stops at the
+/// previous line break or digit, so a label only applies to the nearest
+/// number.
+const WINDOW_BEFORE: usize = 48;
+
+/// Bytes after a match searched for keywords. The window also stops at the
+/// next digit or at any of [`CLAUSE_ENDS`].
+const WINDOW_AFTER: usize = 24;
+
+const CLAUSE_ENDS: &[u8] = b".,;/|\n";
+
+/// An input to the logistic model. Each is 0 or 1 for a match, and its
+/// weight in [`WEIGHTS`] is added to the logit when it is 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Feature {
+    Bias,
+    Valid,
+    Dashed,
+    Spaced,
+    KnownFake,
+    SsnKeyword,
+    TaxIdKeyword,
+    AmbiguousKeyword,
+    NegativeKeyword,
+}
+
+const FEATURES: usize = Feature::NegativeKeyword as usize + 1;
+
+/// The weight of each [`Feature`], fitted to the training split of
+/// `data/confidence_corpus.tsv`. The `weights_are_fitted_to_the_corpus` test
+/// refits them and prints new values when they drift.
+const WEIGHTS: [f32; FEATURES] = [
+    -10.35, // Bias
+    8.76,   // Valid
+    3.4,    // Dashed
+    2.54,   // Spaced
+    -10.97, // KnownFake
+    3.3,    // SsnKeyword
+    5.18,   // TaxIdKeyword
+    2.22,   // AmbiguousKeyword
+    -10.42, // NegativeKeyword
+];
+
+/// Keywords that suggest a TIN, in groups that share a weight. Matched
+/// case-insensitively on word boundaries.
+const KEYWORDS: &[(&str, Feature)] = &[
+    ("ssn", Feature::SsnKeyword),
+    ("ssns", Feature::SsnKeyword),
+    ("social security", Feature::SsnKeyword),
+    ("soc sec", Feature::SsnKeyword),
+    ("ss#", Feature::SsnKeyword),
+    ("itin", Feature::SsnKeyword),
+    ("atin", Feature::SsnKeyword),
+    ("taxpayer", Feature::TaxIdKeyword),
+    ("tax id", Feature::TaxIdKeyword),
+    ("taxid", Feature::TaxIdKeyword),
+    ("tin", Feature::AmbiguousKeyword),
+    ("social", Feature::AmbiguousKeyword),
+];
+
+/// Keywords that suggest some other kind of number.
+const NEGATIVE_KEYWORDS: &[&str] = &[
+    "order", "invoice", "account", "acct", "routing", "tracking", "phone", "tel", "fax", "zip",
+];
+
+/// How a matched number is punctuated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Punctuation {
+    /// `XXX-XX-XXXX`
+    Dashed,
+    /// `XXX XX XXXX`
+    Spaced,
+    /// `XXXXXXXXX`
+    Undashed,
+}
+
+/// The evidence behind a [`Confidence`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Signals {
+    /// The strongest TIN keyword near the match, if any.
+    pub keyword: Option<&'static str>,
+    /// The strongest non-TIN keyword near the match, if any.
+    pub negative_keyword: Option<&'static str>,
+    /// How the number is punctuated.
+    pub punctuation: Punctuation,
+    /// Whether the number validates as an SSN, ITIN, or ATIN.
+    pub valid: bool,
+    /// Whether the number is a well-known example or advertising number.
+    pub known_fake: bool,
+}
+
+/// How likely a match is to be a real TIN, as a score from 0 to 1.
+///
+/// The value is calibrated against a labelled corpus; see the
+/// [module docs](self) for how it is computed and how well it holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Confidence {
+    value: f32,
+    signals: Signals,
+}
+
+impl Confidence {
+    /// Returns the score, from 0 (not a TIN) to 1 (a TIN).
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the evidence the score is based on.
+    pub fn signals(&self) -> &Signals {
+        &self.signals
+    }
+
+    /// Returns `true` if the score is at least `threshold`.
+    pub fn meets(&self, threshold: f32) -> bool {
+        self.value >= threshold
+    }
+}
+
+/// Scores a match found in `haystack`.
+///
+/// `haystack` must be the text the match was found in.
+pub fn score<H: AsRef<[u8]> + ?Sized>(haystack: &H, m: &Match) -> Confidence {
+    let (signals, features) = features(haystack.as_ref(), m);
+    let logit: f32 = features.iter().zip(WEIGHTS).map(|(x, w)| x * w).sum();
+    Confidence {
+        value: 1.0 / (1.0 + (-logit).exp()),
+        signals,
+    }
+}
+
+/// Reads the signals around a match, and the model inputs they set.
+fn features(haystack: &[u8], m: &Match) -> (Signals, [f32; FEATURES]) {
+    let matched = &haystack[m.range()];
+    let punctuation = match matched.get(3) {
+        Some(b'-') => Punctuation::Dashed,
+        Some(b' ') => Punctuation::Spaced,
+        _ => Punctuation::Undashed,
+    };
+    let before = &haystack[m.start().saturating_sub(WINDOW_BEFORE)..m.start()];
+    let before = match before
+        .iter()
+        .rposition(|&b| b.is_ascii_digit() || b == b'\n')
+    {
+        Some(i) => &before[i + 1..],
+        None => before,
+    };
+    let after = &haystack[m.end()..haystack.len().min(m.end() + WINDOW_AFTER)];
+    let after = match after
+        .iter()
+        .position(|&b| b.is_ascii_digit() || CLAUSE_ENDS.contains(&b))
+    {
+        Some(i) => &after[..i],
+        None => after,
+    };
+    // A label before the number wins; one after it only counts when nothing
+    // precedes, since it may instead label the next number.
+    let mut keyword = strongest(before);
+    let mut negative_keyword = find_negative(before);
+    if keyword.is_none() && negative_keyword.is_none() {
+        keyword = strongest(after);
+        negative_keyword = find_negative(after);
+    }
+
+    let signals = Signals {
+        keyword: keyword.map(|(k, _)| k),
+        negative_keyword,
+        punctuation,
+        valid: m.is_valid(),
+        known_fake: is_known_fake(matched),
+    };
+
+    let mut features = [0.0; FEATURES];
+    let mut set = |feature: Feature| features[feature as usize] = 1.0;
+    set(Feature::Bias);
+    if signals.valid {
+        set(Feature::Valid);
+    }
+    match punctuation {
+        Punctuation::Dashed => set(Feature::Dashed),
+        Punctuation::Spaced => set(Feature::Spaced),
+        Punctuation::Undashed => {}
+    }
+    if signals.known_fake {
+        set(Feature::KnownFake);
+    }
+    if let Some((_, tier)) = keyword {
+        set(tier);
+    }
+    if negative_keyword.is_some() {
+        set(Feature::NegativeKeyword);
+    }
+    (signals, features)
+}
+
+/// Returns the TIN keyword with the highest weight found in `window`.
+fn strongest(window: &[u8]) -> Option<(&'static str, Feature)> {
+    KEYWORDS
+        .iter()
+        .filter(|(k, _)| contains_word(window, k))
+        .copied()
+        .reduce(|a, b| {
+            if WEIGHTS[b.1 as usize] > WEIGHTS[a.1 as usize] {
+                b
+            } else {
+                a
+            }
+        })
+}
+
+/// Returns the first non-TIN keyword found in `window`.
+fn find_negative(window: &[u8]) -> Option<&'static str> {
+    NEGATIVE_KEYWORDS
+        .iter()
+        .copied()
+        .find(|k| contains_word(window, k))
+}
+
+/// Returns `true` if `word` occurs in `text`, case-insensitively, not
+/// surrounded by letters.
+fn contains_word(text: &[u8], word: &str) -> bool {
+    let word = word.as_bytes();
+    text.windows(word.len()).enumerate().any(|(i, window)| {
+        window.eq_ignore_ascii_case(word)
+            && !text[..i].last().is_some_and(u8::is_ascii_alphabetic)
+            && !text
+                .get(i + word.len())
+                .is_some_and(u8::is_ascii_alphabetic)
+    })
+}
+
+/// Returns `true` for numbers that are published as examples or reserved for
+/// advertising, and for runs of a single repeated digit.
+fn is_known_fake(matched: &[u8]) -> bool {
+    let mut digits = [0u8; parse::DIGITS];
+    for (digit, b) in digits
+        .iter_mut()
+        .zip(matched.iter().filter(|b| b.is_ascii_digit()))
+    {
+        *digit = b - b'0';
+    }
+    matches!(
+        parse::fold(&digits),
+        123_456_789 | 78_051_120 | 219_099_999 | 987_654_320..=987_654_329
+    ) || digits.iter().all(|&d| d == digits[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FEATURES, WEIGHTS, features};
+    use crate::scan::find_iter;
+
+    fn scores(text: &str) -> Vec<f32> {
+        find_iter(text)
+            .map(|m| m.confidence(text).value())
+            .collect()
+    }
+
+    #[test]
+    fn keywords_raise_confidence() {
+        let [plain, labeled] = scores("id 234-56-7890 / SSN: 234-56-7890")[..] else {
+            panic!("expected two matches");
+        };
+        assert!(plain > 0.5 && plain < labeled, "{plain}");
+        assert!(labeled > 0.9, "{labeled}");
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive_words() {
+        let text = "Social Security Number 234567890";
+        let m = find_iter(text).next().unwrap();
+        assert_eq!(
+            m.confidence(text).signals().keyword,
+            Some("social security")
+        );
+
+        // "tin" inside "routing" is not the TIN keyword.
+        let text = "destination 234567890";
+        let m = find_iter(text).next().unwrap();
+        assert_eq!(m.confidence(text).signals().keyword, None);
+    }
+
+    #[test]
+    fn negative_keywords_lower_confidence() {
+        let [score] = scores("Order #234567890 shipped")[..] else {
+            panic!("expected one match");
+        };
+        assert!(score < 0.1, "{score}");
+    }
+
+    #[test]
+    fn labels_before_a_number_win() {
+        let s = scores("order 234567890 SSN 234-56-7890");
+        assert!(s[0] < 0.1 && s[1] > 0.9, "{s:?}");
+
+        let [score] = scores("234-56-7890 (SSN)")[..] else {
+            panic!("expected one match");
+        };
+        assert!(score > 0.9, "{score}");
+    }
+
+    #[test]
+    fn punctuation_ranks_dashed_over_spaced_over_undashed() {
+        let s = scores("234-56-7890 ; 234 56 7890 ; 234567890");
+        assert!(s[0] > s[1] && s[1] > s[2], "{s:?}");
+    }
+
+    #[test]
+    fn invalid_numbers_score_low() {
+        let [score] = scores("SSN 000-12-3456")[..] else {
+            panic!("expected one match");
+        };
+        assert!(score < 0.5, "{score}");
+
+        // The most a number that fails validation can score.
+        let [score] = scores("Taxpayer ID 000-12-3456")[..] else {
+            panic!("expected one match");
+        };
+        assert!(score <= 0.15, "{score}");
+    }
+
+    #[test]
+    fn known_fakes_score_low() {
+        for text in [
+            "SSN 078-05-1120",
+            "SSN 123-45-6789",
+            "SSN 987-65-4325",
+            "SSN 111-11-1111",
+            "Taxpayer ID 078-05-1120",
+        ] {
+            let m = find_iter(text).next().unwrap();
+            let confidence = m.confidence(text);
+            assert!(confidence.signals().known_fake, "{text}");
+            assert!(!confidence.meets(0.02), "{text}");
+        }
+    }
+
+    #[test]
+    fn table_scores_hold() {
+        let cases = [
+            ("SSN 234-56-7890", 0.99),
+            ("id 234-56-7890", 0.86),
+            ("SSN 234567890", 0.85),
+            ("id 234567890", 0.17),
+            ("order 234567890", 0.0),
+        ];
+        for (text, expected) in cases {
+            let [score] = scores(text)[..] else {
+                panic!("expected one match in {text}");
+            };
+            assert!((score - expected).abs() < 0.01, "{text}: {score}");
+        }
+    }
+
+    #[test]
+    fn separates_labelled_examples() {
+        // (text, is a TIN)
+        let labelled = [
+            ("Employee SSN: 234-56-7890", true),
+            ("social security no. 345 67 8901", true),
+            ("ITIN 912-70-3456 on file", true),
+            ("Taxpayer ID 456-78-9012", true),
+            ("ss# 567891234", true),
+            ("applicant 678-90-1234", true),
+            ("SSN 234-56-7890 (spouse)", true),
+            ("Order #234567890 shipped", false),
+            ("invoice 345678901 due", false),
+            ("account 456789012", false),
+            ("tracking number 567 89 0123", false),
+            ("ref 678901234", false),
+            ("SSN 078-05-1120", false),
+            ("SSN 000-12-3456", false),
+            ("zip 123456789", false),
+        ];
+        for (text, is_tin) in labelled {
+            let [score] = scores(text)[..] else {
+                panic!("expected one match in {text}");
+            };
+            assert_eq!(score >= 0.5, is_tin, "{text}: {score}");
+        }
+    }
+
+    #[test]
+    fn scores_are_between_0_and_1() {
+        for s in scores("SSN 234-56-7890 order 000000000 tax id 900 70 1234") {
+            assert!((0.0..=1.0).contains(&s));
+        }
+    }
+
+    /// A line of `data/confidence_corpus.tsv`: the model inputs for its one
+    /// match, whether it is a TIN, and whether it is held out.
+    struct Example {
+        features: [f32; FEATURES],
+        is_tin: bool,
+        held_out: bool,
+    }
+
+    fn corpus() -> Vec<Example> {
+        include_str!("../data/confidence_corpus.tsv")
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.is_empty())
+            .map(|line| {
+                let mut columns = line.splitn(3, '\t');
+                let (Some(label), Some(split), Some(text)) =
+                    (columns.next(), columns.next(), columns.next())
+                else {
+                    panic!("malformed line: {line}");
+                };
+                let [m] = &find_iter(text).collect::<Vec<_>>()[..] else {
+                    panic!("expected one match in {text}");
+                };
+                Example {
+                    features: features(text.as_bytes(), m).1,
+                    is_tin: label == "1",
+                    held_out: split == "test",
+                }
+            })
+            .collect()
+    }
+
+    const PENALTY: f64 = 0.01;
+
+    fn predict(weights: &[f64; FEATURES], features: &[f32; FEATURES]) -> f64 {
+        let logit: f64 = weights
+            .iter()
+            .zip(features)
+            .map(|(w, &x)| w * f64::from(x))
+            .sum();
+        1.0 / (1.0 + (-logit).exp())
+    }
+
+    /// Fits logistic regression with a small L2 penalty on every weight but
+    /// the bias, by Newton's method. The penalty only keeps weights finite
+    /// for features that separate the corpus, such as a known fake.
+    fn fit(examples: &[&Example]) -> [f64; FEATURES] {
+        let mut weights = [0.0; FEATURES];
+        for _ in 0..50 {
+            // The gradient and Hessian of the penalized negative log
+            // likelihood, with the gradient as an extra column.
+            let mut system = [[0.0; FEATURES + 1]; FEATURES];
+            for (i, row) in system.iter_mut().enumerate().skip(1) {
+                row[i] = PENALTY;
+                row[FEATURES] = PENALTY * weights[i];
+            }
+            for example in examples {
+                let p = predict(&weights, &example.features);
+                let y = if example.is_tin { 1.0 } else { 0.0 };
+                for (i, row) in system.iter_mut().enumerate() {
+                    let xi = f64::from(example.features[i]);
+                    for (j, &xj) in example.features.iter().enumerate() {
+                        row[j] += p * (1.0 - p) * xi * f64::from(xj);
+                    }
+                    row[FEATURES] += (p - y) * xi;
+                }
+            }
+            // Gaussian elimination with partial pivoting.
+            for col in 0..FEATURES {
+                let pivot = (col..FEATURES)
+                    .max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))
+                    .unwrap();
+                system.swap(col, pivot);
+                let pivot_row = system[col];
+                for (i, row) in system.iter_mut().enumerate() {
+                    if i != col {
+                        let factor = row[col] / pivot_row[col];
+                        for (x, p) in row.iter_mut().zip(pivot_row).skip(col) {
+                            *x -= factor * p;
+                        }
+                    }
+                }
+            }
+            let mut step = 0.0f64;
+            for (i, weight) in weights.iter_mut().enumerate() {
+                let delta = system[i][FEATURES] / system[i][i];
+                *weight -= delta;
+                step = step.max(delta.abs());
+            }
+            if step < 1e-9 {
+                break;
+            }
+        }
+        weights
+    }
+
+    #[test]
+    fn weights_are_fitted_to_the_corpus() {
+        let corpus = corpus();
+        let train: Vec<_> = corpus.iter().filter(|e| !e.held_out).collect();
+        let fitted = fit(&train);
+        let rounded = fitted.map(|w| (w * 100.0).round() / 100.0);
+        for (weight, fitted) in WEIGHTS.iter().zip(fitted) {
+            assert!(
+                (f64::from(*weight) - fitted).abs() < 0.01,
+                "WEIGHTS should be {rounded:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn calibrated_on_held_out_lines() {
+        let weights = WEIGHTS.map(f64::from);
+        // (sum of scores, TINs, lines) per tenth of the score range.
+        let mut buckets = [(0.0, 0.0, 0.0); 10];
+        let held_out = corpus().into_iter().filter(|e| e.held_out);
+        for example in held_out {
+            let p = predict(&weights, &example.features);
+            let bucket = &mut buckets[((p * 10.0) as usize).min(9)];
+            bucket.0 += p;
+            bucket.1 += if example.is_tin { 1.0 } else { 0.0 };
+            bucket.2 += 1.0;
+        }
+
+        let total: f64 = buckets.iter().map(|b| b.2).sum();
+        let mut expected_error = 0.0;
+        for (i, &(scores, tins, n)) in buckets.iter().enumerate() {
+            if n == 0.0 {
+                continue;
+            }
+            let (predicted, observed) = (scores / n, tins / n);
+            expected_error += n / total * (predicted - observed).abs();
+            // Within three standard errors of the predicted rate, plus a
+            // small allowance for buckets of very few lines.
+            let tolerance = 3.0 * (predicted * (1.0 - predicted) / n).sqrt() + 0.02;
+            assert!(
+                (predicted - observed).abs() <= tolerance,
+                "bucket {i}: predicted {predicted:.3}, observed {observed:.3} over {n} lines"
+            );
+        }
+        assert!(expected_error < 0.03, "ECE {expected_error:.3}");
+    }
+}
@@ -0,0 +1,250 @@
+//! Conversions between [`Tin`] and its concrete types, and from raw digits.
+
+use crate::{
+    Atin, Ein, FormatError, Itin, ParseError, Ssn, TaxId, Tin, TinKind, TinKinds, components, parse,
+};
+
+impl Tin {
+    /// Returns the SSN, if this is one.
+    pub fn as_ssn(&self) -> Option<&Ssn> {
+        match self {
+            Tin::Ssn(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the ITIN, if this is one.
+    pub fn as_itin(&self) -> Option<&Itin> {
+        match self {
+            Tin::Itin(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the ATIN, if this is one.
+    pub fn as_atin(&self) -> Option<&Atin> {
+        match self {
+            Tin::Atin(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the EIN, if this is one.
+    pub fn as_ein(&self) -> Option<&Ein> {
+        match self {
+            Tin::Ein(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the nine digits, each 0–9, most significant first.
+    ///
+    /// `Tin::try_from` reads digits as an SSN, ITIN, or ATIN, so it returns
+    /// an SSN-family value or an error for an EIN's digits, never the EIN.
+    /// Rebuild an EIN with [`Ein::new`] from its 2-digit prefix and 7-digit
+    /// serial instead.
+    pub fn to_digits(&self) -> [u8; 9] {
+        TaxId::to_digits(self)
+    }
+}
+
+impl From<Ssn> for Tin {
+    fn from(ssn: Ssn) -> Self {
+        Tin::Ssn(ssn)
+    }
+}
+
+impl From<Itin> for Tin {
+    fn from(itin: Itin) -> Self {
+        Tin::Itin(itin)
+    }
+}
+
+impl From<Atin> for Tin {
+    fn from(atin: Atin) -> Self {
+        Tin::Atin(atin)
+    }
+}
+
+impl From<Ein> for Tin {
+    fn from(ein: Ein) -> Self {
+        Tin::Ein(ein)
+    }
+}
+
+/// Fails with [`ParseError::DisallowedKind`] naming the actual kind.
+impl TryFrom<Tin> for Ssn {
+    type Error = ParseError;
+
+    fn try_from(tin: Tin) -> Result<Self, Self::Error> {
+        match tin {
+            Tin::Ssn(v) => Ok(v),
+            other => Err(disallowed(&other, TinKind::Ssn)),
+        }
+    }
+}
+
+/// Fails with [`ParseError::DisallowedKind`] naming the actual kind.
+impl TryFrom<Tin> for Itin {
+    type Error = ParseError;
+
+    fn try_from(tin: Tin) -> Result<Self, Self::Error> {
+        match tin {
+            Tin::Itin(v) => Ok(v),
+            other => Err(disallowed(&other, TinKind::Itin)),
+        }
+    }
+}
+
+/// Fails with [`ParseError::DisallowedKind`] naming the actual kind.
+impl TryFrom<Tin> for Atin {
+    type Error = ParseError;
+
+    fn try_from(tin: Tin) -> Result<Self, Self::Error> {
+        match tin {
+            Tin::Atin(v) => Ok(v),
+            other => Err(disallowed(&other, TinKind::Atin)),
+        }
+    }
+}
+
+/// Fails with [`ParseError::DisallowedKind`] naming the actual kind.
+impl TryFrom<Tin> for Ein {
+    type Error = ParseError;
+
+    fn try_from(tin: Tin) -> Result<Self, Self::Error> {
+        match tin {
+            Tin::Ein(v) => Ok(v),
+            other => Err(disallowed(&other, TinKind::Ein)),
+        }
+    }
+}
+
+fn disallowed(tin: &Tin, wanted: TinKind) -> ParseError {
+    ParseError::DisallowedKind {
+        actual: tin.kind(),
+        allowed: TinKinds::of(wanted),
+    }
+}
+
+/// Reads a number such as `123456789` as an SSN, ITIN, or ATIN, as undashed
+/// input is parsed.
+impl TryFrom<u32> for Tin {
+    type Error = ParseError;
+
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        let area = (number / 1_000_000) as u16;
+        let group = (number / 10_000 % 100) as u8;
+        let serial = (number % 10_000) as u16;
+        Tin::from_components(area, group, serial)
+    }
+}
+
+/// Reads nine digit values, each 0–9, as an SSN, ITIN, or ATIN, as undashed
+/// input is parsed.
+impl TryFrom<[u8; 9]> for Tin {
+    type Error = ParseError;
+
+    fn try_from(digits: [u8; 9]) -> Result<Self, Self::Error> {
+        if let Some(position) = digits.iter().position(|&d| d > 9) {
+            return Err(ParseError::InvalidFormat(FormatError::in_digits(position)));
+        }
+        let (area, group, serial) = components(&digits);
+        Tin::from_components(area, group, serial)
+    }
+}
+
+/// Returns the nine digits of `(area, group, serial)`, most significant first.
+pub(crate) fn digits((area, group, serial): (u16, u8, u16)) -> [u8; parse::DIGITS] {
+    let mut number = u32::from(area) * 1_000_000 + u32::from(group) * 10_000 + u32::from(serial);
+    let mut digits = [0; parse::DIGITS];
+    for digit in digits.iter_mut().rev() {
+        *digit = (number % 10) as u8;
+        number /= 10;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_components;
+
+    #[test]
+    fn concrete_types_round_trip() {
+        let ssn: Ssn = "123-45-6789".parse().unwrap();
+        let tin = Tin::from(ssn.clone());
+        assert_eq!(tin.as_ssn(), Some(&ssn));
+        assert_eq!(tin.as_itin(), None);
+        assert_eq!(Ssn::try_from(tin), Ok(ssn));
+
+        let ein: Ein = "12-3456789".parse().unwrap();
+        assert_eq!(Tin::from(ein.clone()).as_ein(), Some(&ein));
+    }
+
+    #[test]
+    fn wrong_kind_names_the_actual_kind() {
+        let itin: Tin = "900-70-1234".parse().unwrap();
+        assert_eq!(
+            Atin::try_from(itin.clone()),
+            Err(ParseError::DisallowedKind {
+                actual: TinKind::Itin,
+                allowed: TinKinds::ATIN,
+            })
+        );
+        assert_eq!(
+            Ssn::try_from(itin).unwrap_err().to_string(),
+            "ITIN not allowed, expected SSN"
+        );
+    }
+
+    #[test]
+    fn digits_match_parse_components() {
+        for input in ["123-45-6789", "900-70-0001", "900-93-1234"] {
+            let tin: Tin = input.parse().unwrap();
+            let digits = tin.to_digits();
+            let (area, group, serial) = parse_components(input).unwrap();
+            assert_eq!(components(&digits), (area, group, serial));
+            assert_eq!(Tin::try_from(digits).as_ref(), Ok(&tin));
+        }
+    }
+
+    #[test]
+    fn ein_digits_do_not_read_back_as_an_ein() {
+        let ein: Tin = "12-3456789".parse().unwrap();
+        assert_eq!(ein.to_digits(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let tin = Tin::try_from(ein.to_digits()).unwrap();
+        assert_eq!(tin.kind(), TinKind::Ssn);
+        assert_ne!(tin, ein);
+
+        let ein: Tin = "66-6123456".parse().unwrap();
+        assert_eq!(
+            Tin::try_from(ein.to_digits()),
+            Err(ParseError::InvalidArea(666))
+        );
+    }
+
+    #[test]
+    fn numbers_read_as_undashed_input() {
+        assert_eq!(Tin::try_from(123_456_789), "123456789".parse::<Tin>());
+        assert_eq!(Tin::try_from(900_701_234).unwrap().kind(), TinKind::Itin);
+        assert_eq!(Tin::try_from(7_654_321), "007654321".parse::<Tin>());
+        assert_eq!(
+            Tin::try_from(1_000_000_000),
+            Err(ParseError::InvalidArea(1000))
+        );
+        assert_eq!(
+            Tin::try_from(666_123_456),
+            Err(ParseError::InvalidArea(666))
+        );
+    }
+
+    #[test]
+    fn digit_arrays_reject_non_digits() {
+        let Err(ParseError::InvalidFormat(err)) = Tin::try_from([1, 2, 3, 4, 5, 61, 7, 8, 9])
+        else {
+            panic!("expected a format error");
+        };
+        assert_eq!((err.position(), err.len()), (5, 9));
+    }
+}
@@ -0,0 +1,288 @@
+//! Parse errors located in their input, with codes and help text.
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::{CharClass, ParseError, TinKind};
+
+/// A [`ParseError`] together with where in the input it occurred and which
+/// kind of TIN was being parsed.
+///
+/// A diagnostic records only the span, never the input, so it is as safe to
+/// log as the error itself. Form UIs can highlight [`span`](Self::span) in
+/// the field, look up localized copy by [`code`](Self::code), and show
+/// [`help`](Self::help) as a hint.
+///
+/// With the `miette` feature, `Diagnostic` implements `miette::Diagnostic`;
+/// attach the input with `miette::Report::with_source_code` to render the
+/// span.
+///
+/// # Example
+///
+/// ```
+/// use tin::{Diagnostic, Itin, TinKind};
+///
+/// let input = "900-93-1234";
+/// let err = input.parse::<Itin>().unwrap_err();
+/// let diagnostic = Diagnostic::new(input, err, Some(TinKind::Itin));
+/// assert_eq!(diagnostic.code(), "TIN-E003");
+/// assert_eq!(diagnostic.span(), 4..6);
+/// assert_eq!(diagnostic.help().as_deref(), Some("ITIN group 93 belongs to ATIN"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    error: ParseError,
+    target: Option<TinKind>,
+    span: Range<usize>,
+}
+
+impl Diagnostic {
+    /// Locates `error` in `input`. `target` is the kind being parsed, or
+    /// `None` when the kind was detected, as by [`Tin`](crate::Tin).
+    pub fn new(input: &str, error: ParseError, target: Option<TinKind>) -> Self {
+        let span = span(input, &error, target);
+        Self {
+            error,
+            target,
+            span,
+        }
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+
+    /// Returns the kind being parsed, if one was requested.
+    pub fn target(&self) -> Option<TinKind> {
+        self.target
+    }
+
+    /// Returns the stable code of the error, such as `TIN-E002`.
+    pub fn code(&self) -> &'static str {
+        self.error.code()
+    }
+
+    /// Returns the byte range of the input the error refers to.
+    ///
+    /// The range is empty when the input ended early.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the rule the input broke, if there is more to say than the
+    /// error message.
+    pub fn help(&self) -> Option<String> {
+        help(&self.error, self.target)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.error)
+    }
+}
+
+impl std::error::Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Diagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let label = miette::LabeledSpan::at(self.span(), self.error.kind().to_string());
+        Some(Box::new(core::iter::once(label)))
+    }
+}
+
+/// Returns the span of the digits or character the error refers to.
+fn span(input: &str, error: &ParseError, target: Option<TinKind>) -> Range<usize> {
+    let ein = target == Some(TinKind::Ein) || matches!(error, ParseError::InvalidPrefix(_));
+    let digits = match error {
+        ParseError::InvalidFormat(err) => {
+            let mut start = err.position().min(input.len());
+            while !input.is_char_boundary(start) {
+                start -= 1;
+            }
+            let len = match err.class() {
+                CharClass::End => 0,
+                _ => input[start..].chars().next().map_or(0, char::len_utf8),
+            };
+            return start..start + len;
+        }
+        ParseError::InvalidArea(_) => 0..3,
+        ParseError::InvalidGroup(_) => 3..5,
+        ParseError::InvalidSerial(_) if !ein => 5..9,
+        ParseError::InvalidPrefix(_) => 0..2,
+        ParseError::InvalidSerial(_) | ParseError::InvalidEinSerial(_) => 2..9,
+        _ => return 0..input.len(),
+    };
+    let offsets: Vec<usize> = input
+        .bytes()
+        .enumerate()
+        .filter(|(_, b)| b.is_ascii_digit())
+        .map(|(i, _)| i)
+        .collect();
+    if offsets.len() < digits.end {
+        return 0..input.len();
+    }
+    offsets[digits.start]..offsets[digits.end - 1] + 1
+}
+
+/// Explains the rule behind an error.
+fn help(error: &ParseError, target: Option<TinKind>) -> Option<String> {
+    let kind = target.map_or("TIN".to_owned(), |kind| kind.to_string());
+    Some(match (error, target) {
+        (ParseError::InvalidFormat(_), Some(TinKind::Ein)) => {
+            "an EIN is nine digits, written XX-XXXXXXX or XXXXXXXXX".to_owned()
+        }
+        (ParseError::InvalidFormat(_), Some(_)) => {
+            format!("an {kind} is nine digits, written XXX-XX-XXXX or XXXXXXXXX")
+        }
+        (ParseError::InvalidFormat(_), None) => {
+            "a TIN is nine digits, written XXX-XX-XXXX, XX-XXXXXXX, or XXXXXXXXX".to_owned()
+        }
+        (ParseError::InvalidArea(area @ (0 | 666)), _) => {
+            format!("area {area:03} is never issued")
+        }
+        (ParseError::InvalidArea(1000..), _) => "the area is at most 999".to_owned(),
+        (ParseError::InvalidArea(900..), _) => {
+            "areas 900–999 are issued as ITINs and ATINs, not SSNs".to_owned()
+        }
+        (ParseError::InvalidArea(_), Some(TinKind::Itin | TinKind::Atin)) => {
+            format!("{kind} areas are 900–999")
+        }
+        (ParseError::InvalidArea(_), _) => return None,
+        (ParseError::InvalidGroup(0), _) => "group 00 is never issued".to_owned(),
+        (ParseError::InvalidGroup(100..), _) => "the group is at most 99".to_owned(),
+        (ParseError::InvalidGroup(93), Some(TinKind::Itin)) => {
+            "ITIN group 93 belongs to ATIN".to_owned()
+        }
+        (ParseError::InvalidGroup(_), Some(TinKind::Itin)) => {
+            "ITIN groups are 50–65, 70–88, 90–92, and 94–99".to_owned()
+        }
+        (ParseError::InvalidGroup(_), Some(TinKind::Atin)) => {
+            "the ATIN group is always 93".to_owned()
+        }
+        (ParseError::InvalidGroup(_), _) => {
+            "in areas 900–999, groups 50–65, 70–88, 90–92, and 94–99 are ITINs and 93 is ATIN"
+                .to_owned()
+        }
+        (ParseError::InvalidSerial(0), _) => "serial 0000 is never issued".to_owned(),
+        (ParseError::InvalidSerial(_), _) => "the serial is at most 9999".to_owned(),
+        (ParseError::InvalidPrefix(prefix), _) => {
+            format!("EIN prefix {prefix:02} is not assigned by any IRS campus")
+        }
+        (ParseError::InvalidEinSerial(_), _) => "the EIN serial is at most 9999999".to_owned(),
+        (ParseError::DisallowedKind { actual, allowed }, _) => {
+            format!("the input is a valid {actual}, but only {allowed} is accepted")
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ein, ParseOptions, Ssn, Tin};
+
+    fn diagnose<T: core::str::FromStr<Err = ParseError> + fmt::Debug>(
+        input: &str,
+        target: Option<TinKind>,
+    ) -> Diagnostic {
+        Diagnostic::new(input, input.parse::<T>().unwrap_err(), target)
+    }
+
+    #[test]
+    fn spans_cover_the_offending_digits() {
+        let input = " 666 12 3456";
+        let err = Ssn::parse_with(input, &ParseOptions::lenient()).unwrap_err();
+        let d = Diagnostic::new(input, err, Some(TinKind::Ssn));
+        assert_eq!(d.span(), 1..4);
+        assert_eq!(d.help().as_deref(), Some("area 666 is never issued"));
+
+        let d = diagnose::<Ssn>("123450000", Some(TinKind::Ssn));
+        assert_eq!((d.code(), d.span()), ("TIN-E004", 5..9));
+
+        let d = diagnose::<Ein>("07-1234567", Some(TinKind::Ein));
+        assert_eq!(d.span(), 0..2);
+        assert_eq!(
+            d.help().as_deref(),
+            Some("EIN prefix 07 is not assigned by any IRS campus")
+        );
+    }
+
+    #[test]
+    fn format_errors_point_at_one_character() {
+        let d = diagnose::<Ssn>("123-4é-6789", Some(TinKind::Ssn));
+        assert_eq!((d.code(), d.span()), ("TIN-E001", 5..7));
+        let d = diagnose::<Tin>("123-45-678", None);
+        assert_eq!(d.span(), 10..10);
+    }
+
+    #[test]
+    fn spans_stay_on_character_boundaries() {
+        use crate::{IrsId, Ptin};
+
+        let d = diagnose::<Ptin>("P1234567é", None);
+        assert_eq!(d.span(), 8..10);
+        let d = diagnose::<IrsId>("P\u{3000}X٣.٣A.1", None);
+        assert_eq!(d.span(), 1..4);
+
+        // A position inside a character snaps back to its start.
+        let err = ParseError::InvalidFormat(crate::FormatError::at("aé", 2));
+        assert_eq!(Diagnostic::new("aé", err, None).span(), 1..3);
+    }
+
+    #[test]
+    fn help_depends_on_the_target() {
+        let help = |input, target| diagnose::<Tin>(input, target).help();
+        assert_eq!(
+            help("900-12-3456", None).as_deref(),
+            Some(
+                "in areas 900–999, groups 50–65, 70–88, 90–92, and 94–99 are ITINs and 93 is ATIN"
+            )
+        );
+        let ssn = diagnose::<Ssn>("900-70-1234", Some(TinKind::Ssn));
+        assert_eq!(
+            ssn.help().as_deref(),
+            Some("areas 900–999 are issued as ITINs and ATINs, not SSNs")
+        );
+        assert_eq!(ssn.to_string(), "TIN-E002 invalid area number: 900");
+
+        let err = Tin::parse_as("900-93-1234", crate::TinKinds::SSN).unwrap_err();
+        let d = Diagnostic::new("900-93-1234", err, Some(TinKind::Ssn));
+        assert_eq!(
+            d.help().as_deref(),
+            Some("the input is a valid ATIN, but only SSN is accepted")
+        );
+        assert_eq!((d.code(), d.span()), ("TIN-E013", 0..11));
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn miette_labels_the_span() {
+        use miette::Diagnostic as _;
+
+        let d = diagnose::<Ssn>("123-00-6789", Some(TinKind::Ssn));
+        assert_eq!(
+            miette::Diagnostic::code(&d).unwrap().to_string(),
+            "TIN-E003"
+        );
+        let labels: Vec<_> = d.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (4, 2));
+        let report = miette::Report::new(d).with_source_code("123-00-6789");
+        assert_eq!(report.to_string(), "TIN-E003 invalid group number: 0");
+    }
+}
@@ -0,0 +1,306 @@
+//! U.S. Employer Identification Number (EIN) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{FormatError, Masked, ParseError, ParseOptions, parse};
+
+/// A validated U.S. Employer Identification Number.
+///
+/// # Validation
+///
+/// Per [IRS rules](https://www.irs.gov/businesses/small-businesses-self-employed/how-eins-are-assigned-and-valid-ein-prefixes):
+/// - Prefix (first 2 digits) must be one the IRS assigns; see [`Campus`]
+/// - Serial number (last 7 digits) may be 0000000–9999999
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Ein {
+    prefix: u8,
+    serial: u32,
+}
+
+/// The IRS campus, or online channel, that assigned an EIN prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Campus {
+    /// Andover campus (prefixes 10, 12).
+    Andover,
+    /// Atlanta campus (prefixes 60, 67).
+    Atlanta,
+    /// Austin campus (prefixes 50, 53).
+    Austin,
+    /// Brookhaven campus (prefixes 01–06, 11, 13, 14, 16, 21–23, 25, 34, 51, 52, 54–59, 65).
+    Brookhaven,
+    /// Cincinnati campus (prefixes 30, 32, 35–38, 61).
+    Cincinnati,
+    /// Fresno campus (prefixes 15, 24).
+    Fresno,
+    /// Kansas City campus (prefixes 40, 44).
+    KansasCity,
+    /// Memphis campus (prefixes 94, 95).
+    Memphis,
+    /// Ogden campus (prefixes 80, 90).
+    Ogden,
+    /// Philadelphia campus (prefixes 33, 39, 41–43, 48, 62–64, 66, 68, 71–77, 91).
+    Philadelphia,
+    /// Issued online through the IRS EIN assistant (prefixes 20, 26, 27, 45–47, 81–88, 92, 93, 98, 99).
+    Internet,
+    /// Small Business Administration (prefix 31).
+    SmallBusinessAdministration,
+}
+
+impl Campus {
+    /// Returns the campus that assigns the given prefix, or `None` if the IRS
+    /// does not issue EINs with that prefix.
+    pub fn from_prefix(prefix: u8) -> Option<Self> {
+        Some(match prefix {
+            10 | 12 => Campus::Andover,
+            60 | 67 => Campus::Atlanta,
+            50 | 53 => Campus::Austin,
+            1..=6 | 11 | 13 | 14 | 16 | 21..=23 | 25 | 34 | 51 | 52 | 54..=59 | 65 => {
+                Campus::Brookhaven
+            }
+            30 | 32 | 35..=38 | 61 => Campus::Cincinnati,
+            15 | 24 => Campus::Fresno,
+            40 | 44 => Campus::KansasCity,
+            94 | 95 => Campus::Memphis,
+            80 | 90 => Campus::Ogden,
+            33 | 39 | 41..=43 | 48 | 62..=64 | 66 | 68 | 71..=77 | 91 => Campus::Philadelphia,
+            20 | 26 | 27 | 45..=47 | 81..=88 | 92 | 93 | 98 | 99 => Campus::Internet,
+            31 => Campus::SmallBusinessAdministration,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if the EIN was issued online rather than by a campus.
+    pub fn is_online(self) -> bool {
+        self == Campus::Internet
+    }
+}
+
+impl Ein {
+    /// Creates a new EIN from its components.
+    pub fn new(prefix: u8, serial: u32) -> Result<Self, ParseError> {
+        Self::validate(prefix, serial)?;
+        Ok(Self { prefix, serial })
+    }
+
+    fn validate(prefix: u8, serial: u32) -> Result<(), ParseError> {
+        if Campus::from_prefix(prefix).is_none() {
+            return Err(ParseError::InvalidPrefix(prefix));
+        }
+        if serial > 9_999_999 {
+            return Err(ParseError::InvalidEinSerial(serial));
+        }
+        Ok(())
+    }
+
+    /// Returns the prefix (first 2 digits).
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns the serial number (last 7 digits).
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    /// Returns the campus that assigned the prefix.
+    pub fn campus(&self) -> Campus {
+        Campus::from_prefix(self.prefix).expect("prefix is validated by Ein::new")
+    }
+
+    /// Parses an EIN, accepting the spellings allowed by `options`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        let digits = parse::digits_with(s, parse::EIN_BREAKS, options)
+            .map_err(|position| ParseError::InvalidFormat(FormatError::at(s, position)))?;
+        Self::new(parse::fold(&digits[0..2]) as u8, parse::fold(&digits[2..9]))
+    }
+
+    /// Returns the nine digits as a single number.
+    pub(crate) fn number(&self) -> u32 {
+        u32::from(self.prefix) * 10_000_000 + self.serial
+    }
+
+    /// Returns a wrapper whose `Display` masks all but the last 4 digits.
+    pub fn masked(&self) -> Masked<Self> {
+        Masked::new(self.clone())
+    }
+
+    /// Writes the number with all but the last 4 digits masked.
+    pub(crate) fn fmt_masked(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "XX-XXX{:04}", self.serial % 10_000)
+    }
+}
+
+impl FromStr for Ein {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &ParseOptions::new())
+    }
+}
+
+impl fmt::Display for Ein {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}-{:07}", self.prefix, self.serial)
+    }
+}
+
+impl fmt::Debug for Ein {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Ein(")?;
+        self.fmt_masked(f)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Ein {
+    /// Overwrites the components with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.prefix.zeroize();
+        self.serial.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Ein {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.prefix.ct_eq(&other.prefix) & self.serial.ct_eq(&other.serial)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Ein {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Ein {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Ein {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the components.
+        state.write_u32(self.number());
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Ein {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Ein {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_ein_with_dash() {
+        let ein: Ein = "12-3456789".parse().unwrap();
+        assert_eq!(ein.prefix(), 12);
+        assert_eq!(ein.serial(), 3_456_789);
+        assert_eq!(ein.campus(), Campus::Andover);
+        assert_eq!(ein.to_string(), "12-3456789");
+    }
+
+    #[test]
+    fn valid_ein_no_dash() {
+        let ein: Ein = "201234567".parse().unwrap();
+        assert_eq!(ein.to_string(), "20-1234567");
+        assert!(ein.campus().is_online());
+    }
+
+    #[test]
+    fn valid_ein_serial_zero() {
+        let ein = Ein::new(1, 0).unwrap();
+        assert_eq!(ein.to_string(), "01-0000000");
+        assert_eq!(ein.campus(), Campus::Brookhaven);
+    }
+
+    #[test]
+    fn campus_table() {
+        assert_eq!(
+            Campus::from_prefix(31),
+            Some(Campus::SmallBusinessAdministration)
+        );
+        assert_eq!(Campus::from_prefix(46), Some(Campus::Internet));
+        assert_eq!(Campus::from_prefix(91), Some(Campus::Philadelphia));
+        assert_eq!(Campus::from_prefix(95), Some(Campus::Memphis));
+
+        let unassigned = [
+            0, 7, 8, 9, 17, 18, 19, 28, 29, 49, 69, 70, 78, 79, 89, 96, 97,
+        ];
+        for prefix in 0..100 {
+            assert_eq!(
+                Campus::from_prefix(prefix).is_none(),
+                unassigned.contains(&prefix),
+                "prefix {prefix}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        assert!(matches!(
+            "07-1234567".parse::<Ein>(),
+            Err(ParseError::InvalidPrefix(7))
+        ));
+        assert!(matches!(
+            Ein::new(100, 1),
+            Err(ParseError::InvalidPrefix(100))
+        ));
+    }
+
+    #[test]
+    fn invalid_serial_out_of_bounds() {
+        assert!(matches!(
+            Ein::new(12, 10_000_000),
+            Err(ParseError::InvalidEinSerial(10_000_000))
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        assert!(matches!(
+            "123-45-6789".parse::<Ein>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "12-345678".parse::<Ein>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let ein: Ein = "12-3456789".parse().unwrap();
+        assert_eq!(format!("{ein:?}"), "Ein(XX-XXX6789)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut ein: Ein = "12-3456789".parse().unwrap();
+        let same: Ein = "12-3456789".parse().unwrap();
+        let other: Ein = "12-3456780".parse().unwrap();
+        assert_eq!(ein, same);
+        assert_ne!(ein, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&ein), state.hash_one(&same));
+
+        ein.wipe();
+        assert_eq!((ein.prefix(), ein.serial()), (0, 0));
+    }
+}
@@ -0,0 +1,305 @@
+//! Explaining why an input was accepted or rejected.
+
+use core::fmt;
+
+use crate::{Campus, FormatError, ParseError, Tin, TinKind, components, itin, parse};
+
+/// The rules [`explain`] evaluates for each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The input is nine digits in a layout the kind accepts.
+    Format,
+    /// The area number (first 3 digits) is in the kind's range.
+    Area,
+    /// The group number (middle 2 digits) is in the kind's range.
+    Group,
+    /// The serial number (last 4 digits) is in the kind's range.
+    Serial,
+    /// The EIN prefix (first 2 digits) is assigned by an IRS campus.
+    Prefix,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rule::Format => "format",
+            Rule::Area => "area",
+            Rule::Group => "group",
+            Rule::Serial => "serial",
+            Rule::Prefix => "prefix",
+        })
+    }
+}
+
+/// One rule evaluated for one kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    kind: TinKind,
+    rule: Rule,
+    passed: bool,
+    reason: String,
+}
+
+impl Check {
+    fn new(kind: TinKind, rule: Rule, passed: bool, reason: String) -> Self {
+        Self {
+            kind,
+            rule,
+            passed,
+            reason,
+        }
+    }
+
+    /// Returns the kind the rule belongs to.
+    pub fn kind(&self) -> TinKind {
+        self.kind
+    }
+
+    /// Returns the rule.
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns `true` if the input satisfies the rule.
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    /// Returns why the rule passed or failed, such as `area 900 excludes SSN`.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// Every rule evaluated against an input, and the resulting [`Tin`].
+///
+/// Reasons name the area, group, or prefix that broke a rule, but never the
+/// serial number or the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    checks: Vec<Check>,
+    result: Result<TinKind, ParseError>,
+}
+
+impl Trace {
+    /// Returns every check, grouped by kind in the order SSN, ITIN, ATIN,
+    /// EIN. Checks that depend on the format are skipped when it fails.
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    /// Returns `true` if the input satisfies every rule of `kind`.
+    pub fn passed(&self, kind: TinKind) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.kind == kind)
+            .all(Check::passed)
+    }
+
+    /// Returns the kind `Tin` parses the input as, or the error it reports.
+    pub fn result(&self) -> Result<TinKind, &ParseError> {
+        self.result.as_ref().copied()
+    }
+}
+
+/// Lists the reasons of every failed check, separated by semicolons.
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut failed = self.checks.iter().filter(|check| !check.passed);
+        match failed.next() {
+            None => write!(f, "all checks passed"),
+            Some(first) => {
+                f.write_str(&first.reason)?;
+                for check in failed {
+                    write!(f, "; {}", check.reason)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Evaluates every SSN, ITIN, ATIN, and EIN rule against `input`, rather
+/// than stopping at the first failure as parsing does.
+///
+/// # Example
+///
+/// ```
+/// use tin::{TinKind, explain};
+///
+/// let trace = explain("900-10-1234");
+/// assert!(!trace.passed(TinKind::Ssn));
+/// assert_eq!(
+///     trace.to_string(),
+///     "area 900 excludes SSN; group 10 not in ITIN ranges; group 10 != 93 so not ATIN; \
+///      unexpected punctuation at byte 3 of 11"
+/// );
+/// ```
+pub fn explain(input: &str) -> Trace {
+    let mut checks = Vec::new();
+    match parse::digits(input, parse::SSN_BREAKS) {
+        Ok(digits) => {
+            let (area, group, serial) = components(&digits);
+            ssn_checks(&mut checks, area, group, serial);
+            tin_checks(&mut checks, TinKind::Itin, area, group);
+            tin_checks(&mut checks, TinKind::Atin, area, group);
+        }
+        Err(position) => {
+            let reason = FormatError::at(input, position).to_string();
+            for kind in [TinKind::Ssn, TinKind::Itin, TinKind::Atin] {
+                checks.push(Check::new(kind, Rule::Format, false, reason.clone()));
+            }
+        }
+    }
+    match parse::digits(input, parse::EIN_BREAKS) {
+        Ok(digits) => {
+            let prefix = parse::fold(&digits[0..2]) as u8;
+            checks.push(Check::new(
+                TinKind::Ein,
+                Rule::Format,
+                true,
+                "matches XX-XXXXXXX or XXXXXXXXX".to_owned(),
+            ));
+            let passed = Campus::from_prefix(prefix).is_some();
+            let reason = if passed {
+                "prefix is assigned by an IRS campus".to_owned()
+            } else {
+                format!("prefix {prefix:02} is not assigned by any IRS campus")
+            };
+            checks.push(Check::new(TinKind::Ein, Rule::Prefix, passed, reason));
+        }
+        Err(position) => {
+            let reason = FormatError::at(input, position).to_string();
+            checks.push(Check::new(TinKind::Ein, Rule::Format, false, reason));
+        }
+    }
+    Trace {
+        checks,
+        result: input.parse::<Tin>().map(|tin| tin.kind()),
+    }
+}
+
+fn ssn_checks(checks: &mut Vec<Check>, area: u16, group: u8, serial: u16) {
+    let kind = TinKind::Ssn;
+    checks.push(format_check(kind));
+    let (passed, reason) = match area {
+        0 | 666 => (false, format!("area {area:03} is never issued")),
+        900.. => (false, format!("area {area} excludes SSN")),
+        _ => (true, "area is in 001–665 or 667–899".to_owned()),
+    };
+    checks.push(Check::new(kind, Rule::Area, passed, reason));
+    let (passed, reason) = match group {
+        0 => (false, "group 00 is never issued".to_owned()),
+        _ => (true, "group is 01–99".to_owned()),
+    };
+    checks.push(Check::new(kind, Rule::Group, passed, reason));
+    let (passed, reason) = match serial {
+        0 => (false, "serial 0000 is never issued".to_owned()),
+        _ => (true, "serial is 0001–9999".to_owned()),
+    };
+    checks.push(Check::new(kind, Rule::Serial, passed, reason));
+}
+
+/// Adds the checks for ITIN or ATIN, which share the 900 area and accept
+/// any serial number.
+fn tin_checks(checks: &mut Vec<Check>, kind: TinKind, area: u16, group: u8) {
+    checks.push(format_check(kind));
+    let passed = area >= 900;
+    let reason = if passed {
+        "area is 900–999".to_owned()
+    } else {
+        format!("area {area:03} excludes {kind}")
+    };
+    checks.push(Check::new(kind, Rule::Area, passed, reason));
+    let (passed, reason) = if kind == TinKind::Atin {
+        match group {
+            93 => (true, "group is 93".to_owned()),
+            _ => (false, format!("group {group:02} != 93 so not ATIN")),
+        }
+    } else if itin::is_valid_itin_group(group) {
+        (true, "group is in ITIN ranges".to_owned())
+    } else {
+        (false, format!("group {group:02} not in ITIN ranges"))
+    };
+    checks.push(Check::new(kind, Rule::Group, passed, reason));
+    checks.push(Check::new(
+        kind,
+        Rule::Serial,
+        true,
+        "any serial is allowed".to_owned(),
+    ));
+}
+
+fn format_check(kind: TinKind) -> Check {
+    Check::new(
+        kind,
+        Rule::Format,
+        true,
+        "matches XXX-XX-XXXX or XXXXXXXXX".to_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(trace: &Trace) -> Vec<(TinKind, Rule)> {
+        trace
+            .checks()
+            .iter()
+            .filter(|check| !check.passed())
+            .map(|check| (check.kind(), check.rule()))
+            .collect()
+    }
+
+    #[test]
+    fn evaluates_every_kind() {
+        let trace = explain("900701234");
+        assert_eq!(trace.result(), Ok(TinKind::Itin));
+        assert!(trace.passed(TinKind::Itin));
+        assert!(trace.passed(TinKind::Ein));
+        assert_eq!(
+            failed(&trace),
+            [(TinKind::Ssn, Rule::Area), (TinKind::Atin, Rule::Group)]
+        );
+        assert_eq!(trace.checks().len(), 14);
+    }
+
+    #[test]
+    fn reports_every_failure_of_an_invalid_number() {
+        let trace = explain("000-00-0000");
+        assert_eq!(
+            trace.result().unwrap_err().kind(),
+            crate::ErrorKind::InvalidArea
+        );
+        assert_eq!(
+            trace.to_string(),
+            "area 000 is never issued; group 00 is never issued; serial 0000 is never issued; \
+             area 000 excludes ITIN; group 00 not in ITIN ranges; area 000 excludes ATIN; \
+             group 00 != 93 so not ATIN; unexpected punctuation at byte 3 of 11"
+        );
+    }
+
+    #[test]
+    fn format_failures_skip_the_other_rules() {
+        let trace = explain("12-3456789");
+        assert_eq!(trace.result(), Ok(TinKind::Ein));
+        assert_eq!(
+            failed(&trace),
+            [
+                (TinKind::Ssn, Rule::Format),
+                (TinKind::Itin, Rule::Format),
+                (TinKind::Atin, Rule::Format)
+            ]
+        );
+    }
+
+    #[test]
+    fn never_includes_the_serial() {
+        for input in ["900-10-5678", "666-12-5678", "075678912", "12a45678"] {
+            let trace = explain(input);
+            let text: Vec<_> = trace.checks().iter().map(Check::reason).collect();
+            assert!(!text.concat().contains("5678"), "{input}: {text:?}");
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Foreign taxpayer identification numbers for common treaty countries.
+//!
+//! W-8BEN and W-8BEN-E forms carry the beneficial owner's foreign TIN. This
+//! module validates the most common ones:
+//!
+//! | Country | Type | Check |
+//! |---------|------|-------|
+//! | CA | [`Sin`] — Social Insurance Number | Luhn |
+//! | GB | [`Utr`] — Unique Taxpayer Reference | Weighted mod 11 |
+//! | GB | [`Nino`] — National Insurance Number | Prefix and suffix rules |
+//! | MX | [`Rfc`] — Registro Federal de Contribuyentes | Date |
+//! | MX | [`Curp`] — Clave Única de Registro de Población | Date, state, mod 10 |
+//! | IN | [`Pan`] — Permanent Account Number | Holder type |
+//! | DE | [`SteuerId`] — Steuerliche Identifikationsnummer | Digit rules, ISO 7064 mod 11,10 |
+//!
+//! [`ForeignTin`] selects the right type from an ISO 3166-1 alpha-2 country
+//! code, the way [`Tin`](crate::Tin) selects among SSN, ITIN, and ATIN.
+//!
+//! # Example
+//!
+//! ```
+//! use tin::foreign::ForeignTin;
+//!
+//! let tin = ForeignTin::parse("CA", "046 454 286").unwrap();
+//! assert!(matches!(tin, ForeignTin::Sin(_)));
+//! assert_eq!(tin.country(), "CA");
+//! ```
+
+mod curp;
+mod nino;
+mod pan;
+mod rfc;
+mod sin;
+mod steuer_id;
+mod utr;
+
+use core::fmt;
+
+use crate::{FormatError, ParseError};
+
+pub use curp::Curp;
+pub use nino::Nino;
+pub use pan::Pan;
+pub use rfc::Rfc;
+pub use sin::Sin;
+pub use steuer_id::SteuerId;
+pub use utr::Utr;
+
+/// A validated foreign TIN, selected by country.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum ForeignTin {
+    /// Canadian Social Insurance Number.
+    Sin(Sin),
+    /// UK Unique Taxpayer Reference.
+    Utr(Utr),
+    /// UK National Insurance Number.
+    Nino(Nino),
+    /// Mexican Registro Federal de Contribuyentes.
+    Rfc(Rfc),
+    /// Mexican Clave Única de Registro de Población.
+    Curp(Curp),
+    /// Indian Permanent Account Number.
+    Pan(Pan),
+    /// German Steuerliche Identifikationsnummer.
+    SteuerId(SteuerId),
+}
+
+impl ForeignTin {
+    /// Parses a TIN issued by the country with the given ISO 3166-1 alpha-2
+    /// code (case-insensitive).
+    ///
+    /// Where a country issues two kinds, the input's shape selects one: a
+    /// UK value starting with a letter is a NINO, otherwise a UTR; an 18
+    /// character Mexican value is a CURP, otherwise an RFC.
+    pub fn parse(country: &str, s: &str) -> Result<Self, ParseError> {
+        match country.to_ascii_uppercase().as_str() {
+            "CA" => s.parse().map(ForeignTin::Sin),
+            "GB" | "UK" => {
+                if s.trim_start()
+                    .starts_with(|c: char| c.is_ascii_alphabetic())
+                {
+                    s.parse().map(ForeignTin::Nino)
+                } else {
+                    s.parse().map(ForeignTin::Utr)
+                }
+            }
+            "MX" => {
+                if s.chars().filter(|c| c.is_alphanumeric()).count() == 18 {
+                    s.parse().map(ForeignTin::Curp)
+                } else {
+                    s.parse().map(ForeignTin::Rfc)
+                }
+            }
+            "IN" => s.parse().map(ForeignTin::Pan),
+            "DE" => s.parse().map(ForeignTin::SteuerId),
+            _ => Err(ParseError::UnsupportedCountry(match country.as_bytes() {
+                &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                    [a.to_ascii_uppercase(), b.to_ascii_uppercase()]
+                }
+                _ => *b"??",
+            })),
+        }
+    }
+
+    /// Returns the ISO 3166-1 alpha-2 code of the issuing country.
+    pub fn country(&self) -> &'static str {
+        match self {
+            ForeignTin::Sin(_) => "CA",
+            ForeignTin::Utr(_) | ForeignTin::Nino(_) => "GB",
+            ForeignTin::Rfc(_) | ForeignTin::Curp(_) => "MX",
+            ForeignTin::Pan(_) => "IN",
+            ForeignTin::SteuerId(_) => "DE",
+        }
+    }
+}
+
+impl fmt::Display for ForeignTin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForeignTin::Sin(v) => v.fmt(f),
+            ForeignTin::Utr(v) => v.fmt(f),
+            ForeignTin::Nino(v) => v.fmt(f),
+            ForeignTin::Rfc(v) => v.fmt(f),
+            ForeignTin::Curp(v) => v.fmt(f),
+            ForeignTin::Pan(v) => v.fmt(f),
+            ForeignTin::SteuerId(v) => v.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for ForeignTin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForeignTin::Sin(v) => v.fmt(f),
+            ForeignTin::Utr(v) => v.fmt(f),
+            ForeignTin::Nino(v) => v.fmt(f),
+            ForeignTin::Rfc(v) => v.fmt(f),
+            ForeignTin::Curp(v) => v.fmt(f),
+            ForeignTin::Pan(v) => v.fmt(f),
+            ForeignTin::SteuerId(v) => v.fmt(f),
+        }
+    }
+}
+
+/// Collects the ASCII characters of `s` other than `separators` into an
+/// uppercased fixed-size array, or fails if there are not exactly `N`.
+fn compact<const N: usize>(s: &str, separators: &[u8]) -> Result<[u8; N], ParseError> {
+    let mut out = [0u8; N];
+    let mut len = 0;
+    for &b in s.as_bytes() {
+        if separators.contains(&b) {
+            continue;
+        }
+        if !b.is_ascii() || len == N {
+            return Err(invalid_at(s, separators, len));
+        }
+        out[len] = b.to_ascii_uppercase();
+        len += 1;
+    }
+    if len == N {
+        Ok(out)
+    } else {
+        Err(invalid_at(s, separators, len))
+    }
+}
+
+/// Returns a format error at the `index`th character of `s` that is not one
+/// of `separators`, or at the end if there are not that many.
+fn invalid_at(s: &str, separators: &[u8], index: usize) -> ParseError {
+    let position = s
+        .char_indices()
+        .filter(|&(_, c)| !(c.is_ascii() && separators.contains(&(c as u8))))
+        .nth(index)
+        .map_or(s.len(), |(i, _)| i);
+    ParseError::InvalidFormat(FormatError::at(s, position))
+}
+
+/// Writes `Name(XXXX1234)`, masking all but the last `visible` characters.
+fn fmt_masked(f: &mut fmt::Formatter<'_>, name: &str, value: &[u8], visible: usize) -> fmt::Result {
+    let (hidden, shown) = value.split_at(value.len() - visible);
+    write!(f, "{name}(")?;
+    for _ in hidden {
+        f.write_str("X")?;
+    }
+    f.write_str(core::str::from_utf8(shown).map_err(|_| fmt::Error)?)?;
+    f.write_str(")")
+}
+
+/// Returns `true` if `yymmdd` is a plausible calendar date (any century).
+fn is_valid_date(yymmdd: &[u8]) -> bool {
+    let [y1, y2, m1, m2, d1, d2] = yymmdd else {
+        return false;
+    };
+    if !yymmdd.iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let year = (y1 - b'0') * 10 + (y2 - b'0');
+    let month = (m1 - b'0') * 10 + (m2 - b'0');
+    let day = (d1 - b'0') * 10 + (d2 - b'0');
+    let max_day = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        // Without the century, 00 may be a leap year (2000) or not (1900).
+        2 if year.is_multiple_of(4) => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=max_day).contains(&day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_by_country() {
+        assert!(matches!(
+            ForeignTin::parse("CA", "046-454-286"),
+            Ok(ForeignTin::Sin(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("gb", "1955839661"),
+            Ok(ForeignTin::Utr(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("GB", "AB 12 34 56 C"),
+            Ok(ForeignTin::Nino(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("MX", "GODE561231GR8"),
+            Ok(ForeignTin::Rfc(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("MX", "BOXW310820HNERXN09"),
+            Ok(ForeignTin::Curp(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("IN", "ACUPA7085R"),
+            Ok(ForeignTin::Pan(_))
+        ));
+        assert!(matches!(
+            ForeignTin::parse("DE", "36 574 261 809"),
+            Ok(ForeignTin::SteuerId(_))
+        ));
+    }
+
+    #[test]
+    fn reports_country() {
+        let tin = ForeignTin::parse("uk", "AB123456C").unwrap();
+        assert_eq!(tin.country(), "GB");
+        let tin = ForeignTin::parse("de", "36574261809").unwrap();
+        assert_eq!(tin.country(), "DE");
+    }
+
+    #[test]
+    fn unsupported_country() {
+        assert!(matches!(
+            ForeignTin::parse("FR", "1234567890123"),
+            Err(ParseError::UnsupportedCountry(c)) if c == *b"FR"
+        ));
+        let err = ForeignTin::parse("fr", "1234567890123").unwrap_err();
+        assert_eq!(err.to_string(), "unsupported country: FR");
+        let err = ForeignTin::parse("France 1234567890123", "1").unwrap_err();
+        assert_eq!(err, ParseError::UnsupportedCountry(*b"??"));
+        assert_eq!(err.to_string(), "unsupported country: ??");
+    }
+
+    #[test]
+    fn display_and_debug_delegate() {
+        let tin = ForeignTin::parse("CA", "046454286").unwrap();
+        assert_eq!(tin.to_string(), "046 454 286");
+        assert_eq!(format!("{tin:?}"), "Sin(XXXXXX286)");
+    }
+
+    #[test]
+    fn compact_strips_separators() {
+        assert_eq!(compact::<3>("a-b c", b"- "), Ok(*b"ABC"));
+        let position = |s| match compact::<3>(s, b"- ") {
+            Err(ParseError::InvalidFormat(err)) => err.position(),
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(position("a b"), 3);
+        assert_eq!(position("a-b c d"), 6);
+        assert_eq!(position("a\u{d1}c"), 1);
+    }
+
+    #[test]
+    fn date_validation() {
+        assert!(is_valid_date(b"561231"));
+        assert!(is_valid_date(b"000229"));
+        assert!(!is_valid_date(b"010229"));
+        assert!(!is_valid_date(b"561301"));
+        assert!(!is_valid_date(b"560431"));
+        assert!(!is_valid_date(b"560100"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Mexican Clave Única de Registro de Población (CURP) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated Mexican Clave Única de Registro de Población.
+///
+/// # Validation
+///
+/// Per [RENAPO](https://www.gob.mx/curp/):
+/// - Must be 18 characters: 4 letters, a `YYMMDD` birth date, a sex code
+///   (`H`, `M`, or `X`), a 2-letter state code, 3 consonants, a
+///   differentiator, and a check digit
+/// - The state code must be one of the 32 federal entities or `NE` (born abroad)
+/// - The check digit is a weighted sum modulo 10 over the first 17 characters
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Curp {
+    chars: [u8; 18],
+}
+
+/// State codes assigned by RENAPO, plus `NE` for births abroad.
+const STATES: [&[u8; 2]; 33] = [
+    b"AS", b"BC", b"BS", b"CC", b"CL", b"CM", b"CS", b"CH", b"DF", b"DG", b"GT", b"GR", b"HG",
+    b"JC", b"MC", b"MN", b"MS", b"NT", b"NL", b"OC", b"PL", b"QT", b"QR", b"SP", b"SL", b"SR",
+    b"TC", b"TS", b"TL", b"VZ", b"YN", b"ZS", b"NE",
+];
+
+/// Returns the check digit for the first 17 characters.
+fn check_digit(chars: &[u8]) -> u8 {
+    // RENAPO's alphabet places Ñ after N; CURPs never contain it, so it only
+    // shifts the values of O–Z.
+    let value = |b: u8| -> u32 {
+        match b {
+            b'0'..=b'9' => u32::from(b - b'0'),
+            b'A'..=b'N' => u32::from(b - b'A') + 10,
+            _ => u32::from(b - b'A') + 11,
+        }
+    };
+    let sum: u32 = chars
+        .iter()
+        .zip((2..=18).rev())
+        .map(|(&b, w)| value(b) * w)
+        .sum();
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
+
+fn is_consonant(b: u8) -> bool {
+    b.is_ascii_uppercase() && !b"AEIOU".contains(&b)
+}
+
+impl Curp {
+    /// Returns the 2-letter state code of the birth state, or `NE` if born abroad.
+    pub fn state(&self) -> &str {
+        core::str::from_utf8(&self.chars[11..13]).expect("validated as ASCII letters")
+    }
+}
+
+impl FromStr for Curp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |index| Err(super::invalid_at(s, b"", index));
+        let chars: [u8; 18] = super::compact(s, b"")?;
+        let mismatch = chars.iter().enumerate().position(|(i, &b)| match i {
+            0..4 => !b.is_ascii_uppercase(),
+            4..10 => !b.is_ascii_digit(),
+            10 => !b"HMX".contains(&b),
+            11 | 12 => !b.is_ascii_uppercase(),
+            13..16 => !is_consonant(b),
+            16 => !b.is_ascii_alphanumeric(),
+            _ => !b.is_ascii_digit(),
+        });
+        if let Some(index) = mismatch {
+            return invalid(index);
+        }
+        if !super::is_valid_date(&chars[4..10]) {
+            return invalid(4);
+        }
+        if !STATES.iter().any(|state| state[..] == chars[11..13]) {
+            return invalid(11);
+        }
+        if check_digit(&chars[..17]) != chars[17] {
+            return Err(ParseError::InvalidChecksum);
+        }
+        Ok(Self { chars })
+    }
+}
+
+impl fmt::Display for Curp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.chars).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for Curp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "Curp", &self.chars, 4)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Curp {
+    /// Overwrites the characters with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.chars.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Curp {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.chars[..].ct_eq(&other.chars[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Curp {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Curp {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Curp {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the characters.
+        state.write(&self.chars);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Curp {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Curp {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_curp() {
+        let curp: Curp = "boxw310820hnerxn09".parse().unwrap();
+        assert_eq!(curp.to_string(), "BOXW310820HNERXN09");
+        assert_eq!(curp.state(), "NE");
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        assert!(matches!(
+            "BOXW310820HNERXN08".parse::<Curp>(),
+            Err(ParseError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn invalid_segments() {
+        for input in [
+            "BOXW311320HNERXN09", // month 13
+            "BOXW310820ZNERXN09", // sex code
+            "BOXW310820HXXRXN09", // state code
+            "BOXW310820HNEAXN09", // vowel in consonant block
+            "BOXW310820HNERXN0",  // too short
+        ] {
+            assert!(
+                matches!(input.parse::<Curp>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let curp: Curp = "BOXW310820HNERXN09".parse().unwrap();
+        assert_eq!(format!("{curp:?}"), "Curp(XXXXXXXXXXXXXXXN09)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut curp: Curp = "boxw310820hnerxn09".parse().unwrap();
+        let same: Curp = "BOXW310820HNERXN09".parse().unwrap();
+        let mut other = curp.clone();
+        other.chars[17] = b'8';
+        assert_eq!(curp, same);
+        assert_ne!(curp, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&curp), state.hash_one(&same));
+
+        curp.wipe();
+        assert_eq!(curp.chars, [0; 18]);
+    }
+}
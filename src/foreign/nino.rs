@@ -0,0 +1,177 @@
+//! UK National Insurance Number (NINO) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated UK National Insurance Number.
+///
+/// # Validation
+///
+/// Per [HMRC rules](https://www.gov.uk/hmrc-internal-manuals/national-insurance-manual/nim39110):
+/// - Must be 2 prefix letters, 6 digits, and a suffix letter, optionally
+///   separated by spaces (`AB 12 34 56 C`)
+/// - The first prefix letter must not be D, F, I, Q, U, or V
+/// - The second prefix letter must not be D, F, I, O, Q, U, or V
+/// - The prefix must not be BG, GB, KN, NK, NT, TN, or ZZ
+/// - The suffix must be A, B, C, or D
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Nino {
+    chars: [u8; 9],
+}
+
+impl FromStr for Nino {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |index| Err(super::invalid_at(s, b" ", index));
+        let chars: [u8; 9] = super::compact(s, b" ")?;
+        let mismatch = chars.iter().enumerate().position(|(i, b)| match i {
+            0 | 1 => !b.is_ascii_uppercase() || b"DFIQUV".contains(b),
+            2..8 => !b.is_ascii_digit(),
+            _ => !b"ABCD".contains(b),
+        });
+        if let Some(index) = mismatch {
+            return invalid(index);
+        }
+        if chars[1] == b'O'
+            || matches!(
+                &chars[0..2],
+                b"BG" | b"GB" | b"KN" | b"NK" | b"NT" | b"TN" | b"ZZ"
+            )
+        {
+            return invalid(1);
+        }
+        Ok(Self { chars })
+    }
+}
+
+impl fmt::Display for Nino {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.chars).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for Nino {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "Nino", &self.chars, 4)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Nino {
+    /// Overwrites the characters with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.chars.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Nino {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.chars[..].ct_eq(&other.chars[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Nino {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Nino {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Nino {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the characters.
+        state.write(&self.chars);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Nino {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Nino {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_nino() {
+        for input in ["AB123456C", "AB 12 34 56 C", "ab123456c"] {
+            let nino: Nino = input.parse().unwrap();
+            assert_eq!(nino.to_string(), "AB123456C");
+        }
+    }
+
+    #[test]
+    fn invalid_prefix_letters() {
+        for input in [
+            "DA123456A",
+            "AO123456A",
+            "GB123456A",
+            "ZZ123456A",
+            "TN123456A",
+        ] {
+            assert!(
+                matches!(input.parse::<Nino>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_suffix() {
+        assert!(matches!(
+            "AB 12 34 56 E".parse::<Nino>(),
+            Err(ParseError::InvalidFormat(err)) if err.position() == 12
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "AB12345C", "A1123456C", "AB12345XC"] {
+            assert!(
+                matches!(input.parse::<Nino>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let nino: Nino = "AB123456C".parse().unwrap();
+        assert_eq!(format!("{nino:?}"), "Nino(XXXXX456C)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut nino: Nino = "AB 12 34 56 C".parse().unwrap();
+        let same: Nino = "AB123456C".parse().unwrap();
+        let mut other = nino.clone();
+        other.chars[8] = b'D';
+        assert_eq!(nino, same);
+        assert_ne!(nino, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&nino), state.hash_one(&same));
+
+        nino.wipe();
+        assert_eq!(nino.chars, [0; 9]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Indian Permanent Account Number (PAN) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated Indian Permanent Account Number.
+///
+/// # Validation
+///
+/// Per the [Income Tax Department](https://www.incometax.gov.in/iec/foportal/help/all-topics/e-filing-services/pan):
+/// - Must be 5 letters, 4 digits, and a letter (`AAAAA9999A`)
+/// - The fourth letter is the holder type: A, B, C, F, G, H, J, K, L, P, or T
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Pan {
+    chars: [u8; 10],
+}
+
+impl Pan {
+    /// Returns the holder type letter, e.g. `P` for an individual or `C` for
+    /// a company.
+    pub fn holder_type(&self) -> char {
+        char::from(self.chars[3])
+    }
+}
+
+impl FromStr for Pan {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: [u8; 10] = super::compact(s, b"")?;
+        let mismatch = chars.iter().enumerate().position(|(i, b)| match i {
+            3 => !b"ABCFGHJKLPT".contains(b),
+            5..9 => !b.is_ascii_digit(),
+            _ => !b.is_ascii_uppercase(),
+        });
+        if let Some(index) = mismatch {
+            return Err(super::invalid_at(s, b"", index));
+        }
+        Ok(Self { chars })
+    }
+}
+
+impl fmt::Display for Pan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.chars).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for Pan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "Pan", &self.chars, 4)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Pan {
+    /// Overwrites the characters with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.chars.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Pan {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.chars[..].ct_eq(&other.chars[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Pan {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Pan {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Pan {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the characters.
+        state.write(&self.chars);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Pan {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Pan {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_pan() {
+        let pan: Pan = "acupa7085r".parse().unwrap();
+        assert_eq!(pan.to_string(), "ACUPA7085R");
+        assert_eq!(pan.holder_type(), 'P');
+    }
+
+    #[test]
+    fn invalid_holder_type() {
+        assert!(matches!(
+            "ACUZA7085R".parse::<Pan>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "ACUPA7085", "ACUPA70851", "1CUPA7085R", "ACUPA7085RR"] {
+            assert!(
+                matches!(input.parse::<Pan>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let pan: Pan = "ACUPA7085R".parse().unwrap();
+        assert_eq!(format!("{pan:?}"), "Pan(XXXXXX085R)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut pan: Pan = "acupa7085r".parse().unwrap();
+        let same: Pan = "ACUPA7085R".parse().unwrap();
+        let mut other = pan.clone();
+        other.chars[9] = b'S';
+        assert_eq!(pan, same);
+        assert_ne!(pan, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&pan), state.hash_one(&same));
+
+        pan.wipe();
+        assert_eq!(pan.chars, [0; 10]);
+    }
+}
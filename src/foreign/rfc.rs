@@ -0,0 +1,222 @@
+//! Mexican Registro Federal de Contribuyentes (RFC) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated Mexican Registro Federal de Contribuyentes.
+///
+/// # Validation
+///
+/// Per the [SAT](https://www.sat.gob.mx/):
+/// - Individuals: 4 letters, a `YYMMDD` birth date, and a 3-character homoclave
+/// - Companies: 3 letters, a `YYMMDD` incorporation date, and a 3-character homoclave
+/// - Letters are A–Z, `&`, or `Ñ`; hyphens and spaces are ignored
+///
+/// The final homoclave character is a check digit, but the SAT has issued
+/// RFCs whose check digit does not verify, so it is not enforced.
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Rfc {
+    value: String,
+}
+
+impl Rfc {
+    /// Returns `true` if this is a company (12-character) RFC.
+    pub fn is_company(&self) -> bool {
+        self.value.chars().count() == 12
+    }
+}
+
+impl FromStr for Rfc {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |index| Err(super::invalid_at(s, b"- ", index));
+        let value: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| {
+                if c == 'ñ' {
+                    'Ñ'
+                } else {
+                    c.to_ascii_uppercase()
+                }
+            })
+            .collect();
+        let chars: Vec<char> = value.chars().collect();
+        let letters = match chars.len() {
+            12 => 3,
+            13 => 4,
+            len => return invalid(len.min(13)),
+        };
+        let mismatch = chars.iter().enumerate().position(|(i, &c)| {
+            if i < letters {
+                !(c.is_ascii_uppercase() || c == '&' || c == 'Ñ')
+            } else if i < letters + 6 {
+                !c.is_ascii_digit()
+            } else {
+                !(c.is_ascii_uppercase() || c.is_ascii_digit())
+            }
+        });
+        if let Some(index) = mismatch {
+            return invalid(index);
+        }
+        let date: Vec<u8> = chars[letters..letters + 6]
+            .iter()
+            .map(|&c| c as u8)
+            .collect();
+        if !super::is_valid_date(&date) {
+            return invalid(letters);
+        }
+        Ok(Self { value })
+    }
+}
+
+impl fmt::Display for Rfc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl fmt::Debug for Rfc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The homoclave is always ASCII, so the last 3 bytes are whole characters.
+        let value = self.value.as_bytes();
+        f.write_str("Rfc(")?;
+        for _ in 3..self.value.chars().count() {
+            f.write_str("X")?;
+        }
+        f.write_str(core::str::from_utf8(&value[value.len() - 3..]).map_err(|_| fmt::Error)?)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Rfc {
+    /// Overwrites the value with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.value.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Rfc {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.value.as_bytes().ct_eq(other.value.as_bytes())
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Rfc {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Rfc {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Rfc {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        use zeroize::Zeroize;
+        // A single fixed-width write, whatever the length: 13 characters,
+        // up to 4 of them the 2-byte `Ñ`.
+        let mut bytes = [0u8; 17];
+        bytes[..self.value.len()].copy_from_slice(self.value.as_bytes());
+        state.write(&bytes);
+        bytes.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Rfc {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Rfc {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_individual() {
+        let rfc: Rfc = "GODE561231GR8".parse().unwrap();
+        assert!(!rfc.is_company());
+        assert_eq!(rfc.to_string(), "GODE561231GR8");
+    }
+
+    #[test]
+    fn valid_company() {
+        let rfc: Rfc = "MAB-930714-8T4".parse().unwrap();
+        assert!(rfc.is_company());
+        assert_eq!(rfc.to_string(), "MAB9307148T4");
+    }
+
+    #[test]
+    fn valid_with_enye_and_ampersand() {
+        let rfc: Rfc = "peña561231gr8".parse().unwrap();
+        assert_eq!(rfc.to_string(), "PEÑA561231GR8");
+        assert!("A&B930714HT4".parse::<Rfc>().is_ok());
+    }
+
+    #[test]
+    fn invalid_date() {
+        assert!(matches!(
+            "GODE561331GR8".parse::<Rfc>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "GODE560230GR8".parse::<Rfc>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in [
+            "",
+            "GODE561231GR",
+            "GODE561231GR89",
+            "G0DE561231GR8",
+            "GODE561231G-8*",
+        ] {
+            assert!(
+                matches!(input.parse::<Rfc>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let rfc: Rfc = "PEÑA561231GR8".parse().unwrap();
+        assert_eq!(format!("{rfc:?}"), "Rfc(XXXXXXXXXXGR8)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut rfc: Rfc = "MAB-930714-8T4".parse().unwrap();
+        let same: Rfc = "MAB9307148T4".parse().unwrap();
+        let mut other = rfc.clone();
+        other.value.replace_range(11.., "5");
+        assert_eq!(rfc, same);
+        assert_ne!(rfc, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&rfc), state.hash_one(&same));
+
+        rfc.wipe();
+        assert!(rfc.value.is_empty());
+    }
+}
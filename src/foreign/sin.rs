@@ -0,0 +1,166 @@
+//! Canadian Social Insurance Number (SIN) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated Canadian Social Insurance Number.
+///
+/// # Validation
+///
+/// Per [ESDC rules](https://www.canada.ca/en/employment-social-development/services/sin.html):
+/// - Must be 9 digits, optionally grouped `XXX XXX XXX` or `XXX-XXX-XXX`
+/// - Must pass the Luhn check
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Sin {
+    digits: [u8; 9],
+}
+
+/// Returns `true` if the ASCII digits pass the Luhn check.
+fn is_luhn_valid(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &b)| {
+            let d = u32::from(b - b'0');
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+impl FromStr for Sin {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: [u8; 9] = super::compact(s, b" -")?;
+        if let Some(index) = digits.iter().position(|b| !b.is_ascii_digit()) {
+            return Err(super::invalid_at(s, b" -", index));
+        }
+        if !is_luhn_valid(&digits) {
+            return Err(ParseError::InvalidChecksum);
+        }
+        Ok(Self { digits })
+    }
+}
+
+impl fmt::Display for Sin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = core::str::from_utf8(&self.digits).map_err(|_| fmt::Error)?;
+        write!(f, "{} {} {}", &d[0..3], &d[3..6], &d[6..9])
+    }
+}
+
+impl fmt::Debug for Sin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "Sin", &self.digits, 3)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Sin {
+    /// Overwrites the digits with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.digits.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Sin {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.digits[..].ct_eq(&other.digits[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Sin {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Sin {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Sin {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the digits.
+        state.write(&self.digits);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Sin {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Sin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_sin() {
+        for input in ["046 454 286", "046-454-286", "046454286"] {
+            let sin: Sin = input.parse().unwrap();
+            assert_eq!(sin.to_string(), "046 454 286");
+        }
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        assert!(matches!(
+            "046 454 287".parse::<Sin>(),
+            Err(ParseError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "04645428", "0464542860", "04645428a"] {
+            assert!(
+                matches!(input.parse::<Sin>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let sin: Sin = "046454286".parse().unwrap();
+        assert_eq!(format!("{sin:?}"), "Sin(XXXXXX286)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut sin: Sin = "046 454 286".parse().unwrap();
+        let same: Sin = "046454286".parse().unwrap();
+        let mut other = sin.clone();
+        other.digits[8] = 7;
+        assert_eq!(sin, same);
+        assert_ne!(sin, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&sin), state.hash_one(&same));
+
+        sin.wipe();
+        assert_eq!(sin.digits, [0; 9]);
+    }
+}
@@ -0,0 +1,193 @@
+//! German Steuerliche Identifikationsnummer (Steuer-ID) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated German Steuerliche Identifikationsnummer.
+///
+/// # Validation
+///
+/// Per the [BZSt](https://www.bzst.de/DE/Privatpersonen/SteuerlicheIdentifikationsnummer/steuerlicheidentifikationsnummer_node.html):
+/// - Must be 11 digits, optionally separated by spaces, not starting with 0
+/// - In the first 10 digits, exactly one digit appears two or three times,
+///   and no digit appears three times in a row
+/// - The last digit is an ISO 7064 MOD 11,10 check digit
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct SteuerId {
+    digits: [u8; 11],
+}
+
+/// Returns `true` if the first 10 digits follow the repetition rules.
+fn has_valid_repetition(digits: &[u8]) -> bool {
+    let mut counts = [0u8; 10];
+    for &b in digits {
+        counts[usize::from(b - b'0')] += 1;
+    }
+    let mut repeated = counts.iter().filter(|&&c| c > 1);
+    let single_repeat = matches!((repeated.next(), repeated.next()), (Some(2 | 3), None));
+    single_repeat && !digits.windows(3).any(|w| w[0] == w[1] && w[1] == w[2])
+}
+
+/// Returns the ISO 7064 MOD 11,10 check digit for the given digits.
+fn check_digit(digits: &[u8]) -> u8 {
+    let mut product = 10;
+    for &b in digits {
+        let sum = match (u32::from(b - b'0') + product) % 10 {
+            0 => 10,
+            s => s,
+        };
+        product = (sum * 2) % 11;
+    }
+    b'0' + ((11 - product) % 10) as u8
+}
+
+impl FromStr for SteuerId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: [u8; 11] = super::compact(s, b" ")?;
+        let mismatch = digits
+            .iter()
+            .enumerate()
+            .position(|(i, &b)| !b.is_ascii_digit() || (i == 0 && b == b'0'));
+        if let Some(index) = mismatch {
+            return Err(super::invalid_at(s, b" ", index));
+        }
+        if !has_valid_repetition(&digits[..10]) {
+            return Err(super::invalid_at(s, b" ", 0));
+        }
+        if check_digit(&digits[..10]) != digits[10] {
+            return Err(ParseError::InvalidChecksum);
+        }
+        Ok(Self { digits })
+    }
+}
+
+impl fmt::Display for SteuerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.digits).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for SteuerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "SteuerId", &self.digits, 3)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl SteuerId {
+    /// Overwrites the digits with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.digits.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for SteuerId {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.digits[..].ct_eq(&other.digits[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for SteuerId {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for SteuerId {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for SteuerId {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the digits.
+        state.write(&self.digits);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for SteuerId {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for SteuerId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_steuer_id() {
+        let id: SteuerId = "36 574 261 809".parse().unwrap();
+        assert_eq!(id.to_string(), "36574261809");
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        assert!(matches!(
+            "36574261808".parse::<SteuerId>(),
+            Err(ParseError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn invalid_repetition() {
+        for input in [
+            "01234567891", // leading zero
+            "12345678901", // no repeated digit
+            "11123456789", // three in a row
+            "11223456789", // two repeated digits
+        ] {
+            assert!(input.parse::<SteuerId>().is_err(), "{input}");
+        }
+        assert!(matches!(
+            "11123456789".parse::<SteuerId>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "3657426180", "365742618090", "3657426180a"] {
+            assert!(
+                matches!(input.parse::<SteuerId>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let id: SteuerId = "36574261809".parse().unwrap();
+        assert_eq!(format!("{id:?}"), "SteuerId(XXXXXXXX809)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut id: SteuerId = "36 574 261 809".parse().unwrap();
+        let same: SteuerId = "36574261809".parse().unwrap();
+        let mut other = id.clone();
+        other.digits[10] = 8;
+        assert_eq!(id, same);
+        assert_ne!(id, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&id), state.hash_one(&same));
+
+        id.wipe();
+        assert_eq!(id.digits, [0; 11]);
+    }
+}
@@ -0,0 +1,157 @@
+//! UK Unique Taxpayer Reference (UTR) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseError;
+
+/// A validated UK Unique Taxpayer Reference.
+///
+/// # Validation
+///
+/// Per [HMRC](https://www.gov.uk/find-utr-number):
+/// - Must be 10 digits, optionally separated by spaces
+/// - The first digit is a check digit over the other nine, weighted
+///   6, 7, 8, 9, 10, 5, 4, 3, 2 modulo 11
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Utr {
+    digits: [u8; 10],
+}
+
+/// Returns the check digit for the nine digits that follow it.
+fn check_digit(digits: &[u8]) -> u8 {
+    const WEIGHTS: [u32; 9] = [6, 7, 8, 9, 10, 5, 4, 3, 2];
+    const CHECK: &[u8; 11] = b"21987654321";
+    let sum: u32 = digits
+        .iter()
+        .zip(WEIGHTS)
+        .map(|(&b, w)| u32::from(b - b'0') * w)
+        .sum();
+    CHECK[(sum % 11) as usize]
+}
+
+impl FromStr for Utr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: [u8; 10] = super::compact(s, b" ")?;
+        if let Some(index) = digits.iter().position(|b| !b.is_ascii_digit()) {
+            return Err(super::invalid_at(s, b" ", index));
+        }
+        if check_digit(&digits[1..]) != digits[0] {
+            return Err(ParseError::InvalidChecksum);
+        }
+        Ok(Self { digits })
+    }
+}
+
+impl fmt::Display for Utr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.digits).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for Utr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_masked(f, "Utr", &self.digits, 4)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Utr {
+    /// Overwrites the digits with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.digits.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Utr {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.digits[..].ct_eq(&other.digits[..])
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Utr {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Utr {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Utr {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the digits.
+        state.write(&self.digits);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Utr {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Utr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_utr() {
+        let utr: Utr = "19558 39661".parse().unwrap();
+        assert_eq!(utr.to_string(), "1955839661");
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        assert!(matches!(
+            "2955839661".parse::<Utr>(),
+            Err(ParseError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "195583966", "19558396610", "195583966K"] {
+            assert!(
+                matches!(input.parse::<Utr>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let utr: Utr = "1955839661".parse().unwrap();
+        assert_eq!(format!("{utr:?}"), "Utr(XXXXXX9661)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut utr: Utr = "19558 39661".parse().unwrap();
+        let same: Utr = "1955839661".parse().unwrap();
+        let mut other = utr.clone();
+        other.digits[0] = 2;
+        assert_eq!(utr, same);
+        assert_ne!(utr, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&utr), state.hash_one(&same));
+
+        utr.wipe();
+        assert_eq!(utr.digits, [0; 10]);
+    }
+}
@@ -0,0 +1,159 @@
+//! Details of input that does not match the expected layout.
+
+use core::fmt;
+
+/// Where input stopped matching the expected layout.
+///
+/// Only the input's length and the position and class of the offending
+/// character are kept, never the input itself, so the error is safe to log.
+///
+/// # Example
+///
+/// ```
+/// use tin::{CharClass, ParseError, Ssn};
+///
+/// let Err(ParseError::InvalidFormat(err)) = "123-4x-6789".parse::<Ssn>() else {
+///     panic!("expected a format error");
+/// };
+/// assert_eq!((err.len(), err.position(), err.class()), (11, 5, CharClass::Letter));
+/// assert_eq!(err.to_string(), "unexpected letter at byte 5 of 11");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct FormatError {
+    len: usize,
+    position: usize,
+    class: CharClass,
+}
+
+/// The kind of character found where the input went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum CharClass {
+    /// An ASCII digit.
+    Digit,
+    /// An ASCII letter.
+    Letter,
+    /// ASCII punctuation, such as a dash or a dot.
+    Punctuation,
+    /// ASCII whitespace.
+    Whitespace,
+    /// A non-ASCII or control character.
+    Other,
+    /// The input ended early.
+    End,
+}
+
+impl FormatError {
+    /// Describes `input` going wrong at byte `position`, which may be
+    /// `input.len()` if the input ended early.
+    pub fn at(input: &str, position: usize) -> Self {
+        let class = match input.as_bytes().get(position) {
+            None => CharClass::End,
+            Some(b) if b.is_ascii_digit() => CharClass::Digit,
+            Some(b) if b.is_ascii_alphabetic() => CharClass::Letter,
+            Some(b) if b.is_ascii_punctuation() => CharClass::Punctuation,
+            Some(b) if b.is_ascii_whitespace() => CharClass::Whitespace,
+            Some(_) => CharClass::Other,
+        };
+        Self {
+            len: input.len(),
+            position: position.min(input.len()),
+            class,
+        }
+    }
+
+    /// Describes an array of digit values going wrong at `position`, where
+    /// a value is not 0–9.
+    pub(crate) fn in_digits(position: usize) -> Self {
+        Self {
+            len: crate::parse::DIGITS,
+            position,
+            class: CharClass::Other,
+        }
+    }
+
+    /// Returns the length of the input in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the input was empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the byte offset of the offending character, or the input's
+    /// length if it ended early.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the class of the offending character.
+    pub fn class(&self) -> CharClass {
+        self.class
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.class {
+            CharClass::End => write!(f, "unexpected end of input at byte {}", self.position),
+            class => write!(
+                f,
+                "unexpected {class} at byte {} of {}",
+                self.position, self.len
+            ),
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CharClass::Digit => "digit",
+            CharClass::Letter => "letter",
+            CharClass::Punctuation => "punctuation",
+            CharClass::Whitespace => "whitespace",
+            CharClass::Other => "non-ASCII or control character",
+            CharClass::End => "end of input",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_the_offending_byte() {
+        let classes: Vec<_> = ["1", "a", "-", " ", "é", "\u{7}", ""]
+            .iter()
+            .map(|s| FormatError::at(s, 0).class())
+            .collect();
+        assert_eq!(
+            classes,
+            [
+                CharClass::Digit,
+                CharClass::Letter,
+                CharClass::Punctuation,
+                CharClass::Whitespace,
+                CharClass::Other,
+                CharClass::Other,
+                CharClass::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn display_omits_the_input() {
+        assert_eq!(
+            FormatError::at("123-45-678", 10).to_string(),
+            "unexpected end of input at byte 10"
+        );
+        assert_eq!(
+            FormatError::at("123 45 6789", 3).to_string(),
+            "unexpected whitespace at byte 3 of 11"
+        );
+    }
+}
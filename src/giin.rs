@@ -0,0 +1,369 @@
+//! FATCA Global Intermediary Identification Number (GIIN) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{FormatError, ParseError};
+
+/// A validated FATCA Global Intermediary Identification Number.
+///
+/// A GIIN has the form `XXXXXX.XXXXX.XX.XXX`: a FATCA ID, a financial
+/// institution number, a category code, and a country code. GIINs are
+/// published on the IRS FFI list, so unlike the TIN types `Debug` does not
+/// mask them.
+///
+/// # Validation
+///
+/// Per the [IRS FFI list FAQs](https://www.irs.gov/businesses/corporations/ffi-list-faqs):
+/// - FATCA ID (first 6 characters) must be uppercase letters or digits
+/// - Financial institution number (next 5 characters) must be uppercase letters or digits
+/// - Category (next 2 characters) must be one of `LE`, `SL`, `ME`, `BR`, or `SP`
+/// - Country (last 3 characters) must be an assigned ISO 3166-1 numeric code
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Giin {
+    fatca_id: [u8; 6],
+    fi_number: [u8; 5],
+    category: GiinCategory,
+    country: u16,
+}
+
+/// The financial institution category encoded in a GIIN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GiinCategory {
+    /// `LE` — lead of an expanded affiliated group.
+    Lead,
+    /// `SL` — single financial institution, not part of a group.
+    Single,
+    /// `ME` — member of an expanded affiliated group.
+    Member,
+    /// `BR` — branch of a financial institution.
+    Branch,
+    /// `SP` — sponsored entity.
+    Sponsored,
+}
+
+impl GiinCategory {
+    /// Returns the category for a two-letter code, or `None` if unknown.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "LE" => GiinCategory::Lead,
+            "SL" => GiinCategory::Single,
+            "ME" => GiinCategory::Member,
+            "BR" => GiinCategory::Branch,
+            "SP" => GiinCategory::Sponsored,
+            _ => return None,
+        })
+    }
+
+    /// Returns the two-letter code.
+    pub fn code(self) -> &'static str {
+        match self {
+            GiinCategory::Lead => "LE",
+            GiinCategory::Single => "SL",
+            GiinCategory::Member => "ME",
+            GiinCategory::Branch => "BR",
+            GiinCategory::Sponsored => "SP",
+        }
+    }
+}
+
+/// Returns `true` if `b` is an uppercase ASCII letter or digit.
+fn is_alphanumeric(b: u8) -> bool {
+    b.is_ascii_uppercase() || b.is_ascii_digit()
+}
+
+/// Reads a segment of exactly `N` uppercase letters or digits.
+fn segment<const N: usize>(s: &str) -> Result<[u8; N], ParseError> {
+    let bytes = s.as_bytes();
+    let position = bytes
+        .iter()
+        .take(N)
+        .position(|&b| !is_alphanumeric(b))
+        .unwrap_or(N);
+    match bytes.try_into() {
+        Ok(segment) if position == N => Ok(segment),
+        _ => Err(ParseError::InvalidFormat(FormatError::at(s, position))),
+    }
+}
+
+/// The assigned ISO 3166-1 numeric country codes, sorted.
+const ISO_3166_NUMERIC: &[u16] = &[
+    4, 8, 10, 12, 16, 20, 24, 28, 31, 32, 36, 40, 44, 48, 50, 51, 52, 56, 60, 64, 68, 70, 72, 74,
+    76, 84, 86, 90, 92, 96, 100, 104, 108, 112, 116, 120, 124, 132, 136, 140, 144, 148, 152, 156,
+    158, 162, 166, 170, 174, 175, 178, 180, 184, 188, 191, 192, 196, 203, 204, 208, 212, 214, 218,
+    222, 226, 231, 232, 233, 234, 238, 239, 242, 246, 248, 250, 254, 258, 260, 262, 266, 268, 270,
+    275, 276, 288, 292, 296, 300, 304, 308, 312, 316, 320, 324, 328, 332, 334, 336, 340, 344, 348,
+    352, 356, 360, 364, 368, 372, 376, 380, 384, 388, 392, 398, 400, 404, 408, 410, 414, 417, 418,
+    422, 426, 428, 430, 434, 438, 440, 442, 446, 450, 454, 458, 462, 466, 470, 474, 478, 480, 484,
+    492, 496, 498, 499, 500, 504, 508, 512, 516, 520, 524, 528, 531, 533, 534, 535, 540, 548, 554,
+    558, 562, 566, 570, 574, 578, 580, 581, 583, 584, 585, 586, 591, 598, 600, 604, 608, 612, 616,
+    620, 624, 626, 630, 634, 638, 642, 643, 646, 652, 654, 659, 660, 662, 663, 666, 670, 674, 678,
+    682, 686, 688, 690, 694, 702, 703, 704, 705, 706, 710, 716, 724, 728, 729, 732, 740, 744, 748,
+    752, 756, 760, 762, 764, 768, 772, 776, 780, 784, 788, 792, 795, 796, 798, 800, 804, 807, 818,
+    826, 831, 832, 833, 834, 840, 850, 854, 858, 860, 862, 876, 882, 887, 894,
+];
+
+impl Giin {
+    /// Creates a new GIIN from its components.
+    pub fn new(
+        fatca_id: &str,
+        fi_number: &str,
+        category: GiinCategory,
+        country: u16,
+    ) -> Result<Self, ParseError> {
+        let fatca_id: [u8; 6] = segment(fatca_id)?;
+        let fi_number: [u8; 5] = segment(fi_number)?;
+        if ISO_3166_NUMERIC.binary_search(&country).is_err() {
+            return Err(ParseError::InvalidCountryCode(country));
+        }
+        Ok(Self {
+            fatca_id,
+            fi_number,
+            category,
+            country,
+        })
+    }
+
+    /// Returns the FATCA ID (first 6 characters).
+    pub fn fatca_id(&self) -> &str {
+        core::str::from_utf8(&self.fatca_id).expect("validated as ASCII by Giin::new")
+    }
+
+    /// Returns the financial institution number (middle 5 characters).
+    pub fn fi_number(&self) -> &str {
+        core::str::from_utf8(&self.fi_number).expect("validated as ASCII by Giin::new")
+    }
+
+    /// Returns the financial institution category.
+    pub fn category(&self) -> GiinCategory {
+        self.category
+    }
+
+    /// Returns the ISO 3166-1 numeric country code (last 3 digits).
+    pub fn country(&self) -> u16 {
+        self.country
+    }
+}
+
+impl FromStr for Giin {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `A`: an uppercase letter or digit, `L`: an uppercase letter, `9`: a digit.
+        const LAYOUT: &[u8] = b"AAAAAA.AAAAA.LL.999";
+        let bytes = s.as_bytes();
+        let mismatch = LAYOUT
+            .iter()
+            .enumerate()
+            .position(|(i, &expected)| {
+                let Some(&b) = bytes.get(i) else {
+                    return true;
+                };
+                match expected {
+                    b'A' => !is_alphanumeric(b),
+                    b'L' => !b.is_ascii_uppercase(),
+                    b'9' => !b.is_ascii_digit(),
+                    _ => b != expected,
+                }
+            })
+            .or((bytes.len() > LAYOUT.len()).then_some(LAYOUT.len()));
+        if let Some(position) = mismatch {
+            return Err(ParseError::InvalidFormat(FormatError::at(s, position)));
+        }
+        let category =
+            GiinCategory::from_code(&s[13..15]).ok_or(ParseError::InvalidGiinCategory)?;
+        let country = s[16..19].parse().expect("three ASCII digits fit in u16");
+        Self::new(&s[0..6], &s[7..12], category, country)
+    }
+}
+
+impl fmt::Display for Giin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{:03}",
+            self.fatca_id(),
+            self.fi_number(),
+            self.category.code(),
+            self.country
+        )
+    }
+}
+
+impl fmt::Debug for Giin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Giin({self})")
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Giin {
+    /// Overwrites the identifying characters and country with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.fatca_id.zeroize();
+        self.fi_number.zeroize();
+        self.country.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Giin {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.fatca_id[..].ct_eq(&other.fatca_id[..])
+            & self.fi_number[..].ct_eq(&other.fi_number[..])
+            & (self.category as u8).ct_eq(&(other.category as u8))
+            & self.country.ct_eq(&other.country)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Giin {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Giin {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Giin {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the components.
+        let mut bytes = [0u8; 14];
+        bytes[..6].copy_from_slice(&self.fatca_id);
+        bytes[6..11].copy_from_slice(&self.fi_number);
+        bytes[11] = self.category as u8;
+        bytes[12..].copy_from_slice(&self.country.to_be_bytes());
+        state.write(&bytes);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Giin {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Giin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_giin() {
+        let giin: Giin = "98Q96B.00000.LE.250".parse().unwrap();
+        assert_eq!(giin.fatca_id(), "98Q96B");
+        assert_eq!(giin.fi_number(), "00000");
+        assert_eq!(giin.category(), GiinCategory::Lead);
+        assert_eq!(giin.country(), 250);
+        assert_eq!(giin.to_string(), "98Q96B.00000.LE.250");
+    }
+
+    #[test]
+    fn valid_categories() {
+        for (code, category) in [
+            ("LE", GiinCategory::Lead),
+            ("SL", GiinCategory::Single),
+            ("ME", GiinCategory::Member),
+            ("BR", GiinCategory::Branch),
+            ("SP", GiinCategory::Sponsored),
+        ] {
+            let giin: Giin = format!("A1B2C3.D4E5F.{code}.840").parse().unwrap();
+            assert_eq!(giin.category(), category);
+            assert_eq!(category.code(), code);
+        }
+    }
+
+    #[test]
+    fn new_from_components() {
+        let giin = Giin::new("A1B2C3", "00001", GiinCategory::Branch, 36).unwrap();
+        assert_eq!(giin.to_string(), "A1B2C3.00001.BR.036");
+    }
+
+    #[test]
+    fn invalid_category() {
+        assert!(matches!(
+            "98Q96B.00000.XX.250".parse::<Giin>(),
+            Err(ParseError::InvalidGiinCategory)
+        ));
+    }
+
+    #[test]
+    fn invalid_country() {
+        assert!(matches!(
+            "98Q96B.00000.LE.000".parse::<Giin>(),
+            Err(ParseError::InvalidCountryCode(0))
+        ));
+        assert!(matches!(
+            Giin::new("98Q96B", "00000", GiinCategory::Lead, 1000),
+            Err(ParseError::InvalidCountryCode(1000))
+        ));
+        // Numeric in range, but not assigned to any country.
+        for country in [1, 999] {
+            assert!(matches!(
+                Giin::new("98Q96B", "00000", GiinCategory::Lead, country),
+                Err(ParseError::InvalidCountryCode(c)) if c == country
+            ));
+        }
+        assert!(matches!(
+            "98Q96B.00000.LE.999".parse::<Giin>(),
+            Err(ParseError::InvalidCountryCode(999))
+        ));
+        assert!(ISO_3166_NUMERIC.is_sorted());
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in [
+            "",
+            "98Q96B00000LE250",
+            "98Q96B.00000.LE",
+            "98Q96B.00000.LE.250.1",
+            "98q96b.00000.LE.250",
+            "98Q96.00000.LE.250",
+            "98Q96B.0000.LE.250",
+            "98Q96B.00000.le.250",
+            "98Q96B.00000.LE.25",
+            "98Q96B.00000.LE.2X0",
+        ] {
+            assert!(
+                matches!(input.parse::<Giin>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_shows_public_value() {
+        let giin: Giin = "98Q96B.00000.LE.250".parse().unwrap();
+        assert_eq!(format!("{giin:?}"), "Giin(98Q96B.00000.LE.250)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut giin: Giin = "98Q96B.00000.LE.250".parse().unwrap();
+        let same: Giin = "98Q96B.00000.LE.250".parse().unwrap();
+        let mut other = giin.clone();
+        other.country = 276;
+        assert_eq!(giin, same);
+        assert_ne!(giin, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&giin), state.hash_one(&same));
+
+        giin.wipe();
+        assert_eq!(
+            (giin.fatca_id, giin.fi_number, giin.country),
+            ([0; 6], [0; 5], 0)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Parsing of any IRS-issued identifier.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Giin, ParseError, Ptin, Tin};
+
+/// Any identifier issued by the IRS or SSA that this crate understands.
+///
+/// Input starting with `P` is parsed as a [`Ptin`], input containing a `.` as
+/// a [`Giin`], and anything else as a [`Tin`].
+///
+/// # Example
+///
+/// ```
+/// use tin::{IrsId, Tin};
+///
+/// let id: IrsId = "P01234567".parse().unwrap();
+/// assert!(matches!(id, IrsId::Ptin(_)));
+///
+/// let id: IrsId = "123-45-6789".parse().unwrap();
+/// assert!(matches!(id, IrsId::Tin(Tin::Ssn(_))));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum IrsId {
+    /// Taxpayer Identification Number (SSN, ITIN, ATIN, or EIN).
+    Tin(Tin),
+    /// Preparer Tax Identification Number.
+    Ptin(Ptin),
+    /// FATCA Global Intermediary Identification Number.
+    Giin(Giin),
+}
+
+impl FromStr for IrsId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with('P') {
+            s.parse().map(IrsId::Ptin)
+        } else if s.contains('.') {
+            s.parse().map(IrsId::Giin)
+        } else {
+            s.parse().map(IrsId::Tin)
+        }
+    }
+}
+
+impl fmt::Display for IrsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrsId::Tin(v) => v.fmt(f),
+            IrsId::Ptin(v) => v.fmt(f),
+            IrsId::Giin(v) => v.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for IrsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrsId::Tin(v) => v.fmt(f),
+            IrsId::Ptin(v) => v.fmt(f),
+            IrsId::Giin(v) => v.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_on_prefix() {
+        assert!(matches!("P01234567".parse::<IrsId>(), Ok(IrsId::Ptin(_))));
+        assert!(matches!(
+            "900-70-1234".parse::<IrsId>(),
+            Ok(IrsId::Tin(Tin::Itin(_)))
+        ));
+        assert!(matches!(
+            "12-3456789".parse::<IrsId>(),
+            Ok(IrsId::Tin(Tin::Ein(_)))
+        ));
+        assert!(matches!(
+            "98Q96B.00000.LE.250".parse::<IrsId>(),
+            Ok(IrsId::Giin(_))
+        ));
+    }
+
+    #[test]
+    fn errors_come_from_the_selected_type() {
+        assert!(matches!(
+            "P00000000".parse::<IrsId>(),
+            Err(ParseError::InvalidPtinNumber(0))
+        ));
+        assert!(matches!(
+            "666-45-6789".parse::<IrsId>(),
+            Err(ParseError::InvalidArea(666))
+        ));
+    }
+
+    #[test]
+    fn display_and_debug_delegate() {
+        let id: IrsId = "P01234567".parse().unwrap();
+        assert_eq!(id.to_string(), "P01234567");
+        assert_eq!(format!("{id:?}"), "Ptin(PXXXX4567)");
+
+        let id: IrsId = "123-45-6789".parse().unwrap();
+        assert_eq!(format!("{id:?}"), "Ssn(XXX-XX-6789)");
+    }
+}
@@ -1,9 +1,13 @@
 //! U.S. Individual Taxpayer Identification Number (ITIN) validation.
 
 use core::fmt;
+use core::ops::RangeInclusive;
 use core::str::FromStr;
 
-use crate::{ParseError, parse_components};
+use crate::{
+    Masked, ParseError, ParseOptions, TaxId, TaxIdRanges, TinKind, parse_components,
+    parse_components_with,
+};
 
 /// A validated U.S. Individual Taxpayer Identification Number.
 ///
@@ -13,7 +17,8 @@ use crate::{ParseError, parse_components};
 /// - Area number (first 3 digits) must be 900–999
 /// - Group number (middle 2 digits) must be 50–65, 70–88, 90–92, or 94–99
 /// - Serial number (last 4 digits) may be 0000–9999
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
 pub struct Itin {
     area: u16,
     group: u8,
@@ -36,6 +41,42 @@ impl Itin {
         })
     }
 
+    /// Returns the area number (first 3 digits).
+    pub fn area(&self) -> u16 {
+        self.area
+    }
+
+    /// Returns the group number (middle 2 digits).
+    pub fn group(&self) -> u8 {
+        self.group
+    }
+
+    /// Returns the serial number (last 4 digits).
+    pub fn serial(&self) -> u16 {
+        self.serial
+    }
+
+    /// Parses an ITIN, accepting the spellings allowed by `options`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        let (area, group, serial) = parse_components_with(s, options)?;
+        Self::new(area, group, serial)
+    }
+
+    /// Returns a wrapper whose `Display` masks all but the last 4 digits.
+    pub fn masked(&self) -> Masked<Self> {
+        Masked::new(self.clone())
+    }
+}
+
+impl TaxIdRanges for Itin {
+    const AREAS: &'static [RangeInclusive<u16>] = &[900..=999];
+    const GROUPS: &'static [RangeInclusive<u8>] = &[50..=65, 70..=88, 90..=92, 94..=99];
+    const SERIALS: RangeInclusive<u16> = 0..=9999;
+
+    fn new(area: u16, group: u8, serial: u16) -> Result<Self, ParseError> {
+        Itin::new(area, group, serial)
+    }
+
     fn validate(area: u16, group: u8, serial: u16) -> Result<(), ParseError> {
         if !(900..=999).contains(&area) {
             return Err(ParseError::InvalidArea(area));
@@ -48,20 +89,19 @@ impl Itin {
         }
         Ok(())
     }
+}
 
-    /// Returns the area number (first 3 digits).
-    pub fn area(&self) -> u16 {
-        self.area
+impl TaxId for Itin {
+    fn components(&self) -> (u16, u8, u16) {
+        (self.area, self.group, self.serial)
     }
 
-    /// Returns the group number (middle 2 digits).
-    pub fn group(&self) -> u8 {
-        self.group
+    fn kind(&self) -> TinKind {
+        TinKind::Itin
     }
 
-    /// Returns the serial number (last 4 digits).
-    pub fn serial(&self) -> u16 {
-        self.serial
+    fn fmt_masked(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "XXX-XX-{:04}", self.serial)
     }
 }
 
@@ -82,10 +122,65 @@ impl fmt::Display for Itin {
 
 impl fmt::Debug for Itin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Itin(XXX-XX-{:04})", self.serial)
+        f.write_str("Itin(")?;
+        self.fmt_masked(f)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Itin {
+    /// Overwrites the components with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.area.zeroize();
+        self.group.zeroize();
+        self.serial.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Itin {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.area.ct_eq(&other.area)
+            & self.group.ct_eq(&other.group)
+            & self.serial.ct_eq(&other.serial)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Itin {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Itin {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Itin {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the components.
+        state.write_u32(
+            u32::from(self.area) * 1_000_000
+                + u32::from(self.group) * 10_000
+                + u32::from(self.serial),
+        );
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Itin {
+    fn drop(&mut self) {
+        self.wipe();
     }
 }
 
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Itin {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,4 +280,21 @@ mod tests {
         let itin: Itin = "900-70-1234".parse().unwrap();
         assert_eq!(format!("{itin:?}"), "Itin(XXX-XX-1234)");
     }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut itin: Itin = "900-70-1234".parse().unwrap();
+        let same: Itin = "900-70-1234".parse().unwrap();
+        let other: Itin = "900-70-1235".parse().unwrap();
+        assert_eq!(itin, same);
+        assert_ne!(itin, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&itin), state.hash_one(&same));
+
+        itin.wipe();
+        assert_eq!((itin.area(), itin.group(), itin.serial()), (0, 0, 0));
+    }
 }
@@ -0,0 +1,180 @@
+//! The last four digits of a TIN.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Atin, Ein, FormatError, Itin, MaskedTin, ParseError, Ssn, Tin};
+
+/// The last four digits of a TIN, as kept by systems that store only those.
+///
+/// Parses four bare digits (`1234`) or any masked notation [`MaskedTin`]
+/// accepts (`XXX-XX-1234`, `***-**-1234`). [`matches`](Self::matches)
+/// compares against a full number, in constant time with the `hardened`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use tin::{Last4, Ssn};
+///
+/// let ssn: Ssn = "123-45-6789".parse().unwrap();
+/// assert!("6789".parse::<Last4>().unwrap().matches(&ssn));
+/// assert!("###-##-6789".parse::<Last4>().unwrap().matches(&ssn));
+/// assert!(!"6780".parse::<Last4>().unwrap().matches(&ssn));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Last4 {
+    digits: u16,
+}
+
+impl Last4 {
+    /// Creates a value from the last four digits as a number, 0–9999.
+    pub fn new(digits: u16) -> Result<Self, ParseError> {
+        if digits > 9999 {
+            return Err(ParseError::InvalidSerial(digits));
+        }
+        Ok(Self { digits })
+    }
+
+    /// Returns the four digits as a number.
+    pub fn digits(&self) -> u16 {
+        self.digits
+    }
+
+    /// Returns `true` if `tin` ends in these four digits.
+    ///
+    /// With the `hardened` feature the comparison uses
+    /// `subtle::ConstantTimeEq`; without it, it is a plain `==` and may
+    /// take less time when the digits differ.
+    pub fn matches(&self, tin: impl Into<Last4>) -> bool {
+        ct_eq(self.digits, tin.into().digits)
+    }
+}
+
+/// Compares two values in constant time.
+#[cfg(feature = "hardened")]
+pub(crate) fn ct_eq(a: u16, b: u16) -> bool {
+    use subtle::ConstantTimeEq;
+    a.ct_eq(&b).into()
+}
+
+/// Compares two values; constant time only with the `hardened` feature.
+#[cfg(not(feature = "hardened"))]
+pub(crate) fn ct_eq(a: u16, b: u16) -> bool {
+    a == b
+}
+
+impl FromStr for Last4 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        if bytes.len() > 4 || bytes.first().is_some_and(|b| !b.is_ascii_digit()) {
+            return s.parse::<MaskedTin>().map(|masked| masked.last4());
+        }
+        if let Some(position) = (0..4).find(|&i| bytes.get(i).is_none_or(|b| !b.is_ascii_digit())) {
+            return Err(ParseError::InvalidFormat(FormatError::at(s, position)));
+        }
+        let digits = bytes
+            .iter()
+            .fold(0, |acc, &b| acc * 10 + u16::from(b - b'0'));
+        Ok(Self { digits })
+    }
+}
+
+impl From<&Ssn> for Last4 {
+    fn from(ssn: &Ssn) -> Self {
+        Self {
+            digits: ssn.serial(),
+        }
+    }
+}
+
+impl From<&Itin> for Last4 {
+    fn from(itin: &Itin) -> Self {
+        Self {
+            digits: itin.serial(),
+        }
+    }
+}
+
+impl From<&Atin> for Last4 {
+    fn from(atin: &Atin) -> Self {
+        Self {
+            digits: atin.serial(),
+        }
+    }
+}
+
+impl From<&Ein> for Last4 {
+    fn from(ein: &Ein) -> Self {
+        Self {
+            digits: (ein.serial() % 10_000) as u16,
+        }
+    }
+}
+
+impl From<&Tin> for Last4 {
+    fn from(tin: &Tin) -> Self {
+        Self {
+            digits: tin.serial(),
+        }
+    }
+}
+
+impl fmt::Display for Last4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.digits)
+    }
+}
+
+impl fmt::Debug for Last4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Last4({:04})", self.digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_and_masked_digits() {
+        assert_eq!("0042".parse::<Last4>().unwrap().digits(), 42);
+        assert_eq!("XXX-XX-1234".parse::<Last4>().unwrap().to_string(), "1234");
+        assert_eq!(format!("{:?}", Last4::new(7).unwrap()), "Last4(0007)");
+        assert!(Last4::new(10_000).is_err());
+    }
+
+    #[test]
+    fn rejects_other_input() {
+        for (input, position) in [("", 0), ("123", 3), ("12a4", 2), ("-123", 0)] {
+            assert!(
+                matches!(
+                    input.parse::<Last4>(),
+                    Err(ParseError::InvalidFormat(err)) if err.position() == position
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_every_type() {
+        let last4: Last4 = "1234".parse().unwrap();
+        assert!(last4.matches(&"900-70-1234".parse::<Itin>().unwrap()));
+        assert!(last4.matches(&"900-93-1234".parse::<Atin>().unwrap()));
+        assert!(last4.matches(&"12-3451234".parse::<Ein>().unwrap()));
+        assert!(last4.matches(&"123-45-1234".parse::<Tin>().unwrap()));
+        assert!(!last4.matches(&"123-45-1235".parse::<Ssn>().unwrap()));
+    }
+
+    #[test]
+    fn constant_time_eq() {
+        assert!(ct_eq(0, 0));
+        assert!(ct_eq(9999, 9999));
+        assert!(!ct_eq(0, 1));
+        assert!(!ct_eq(0x8000, 0));
+        assert!(!ct_eq(0, u16::MAX));
+    }
+}
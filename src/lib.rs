@@ -6,10 +6,22 @@
 //! - **ITIN** — Individual Taxpayer Identification Number (area 900–999, specific groups)
 //! - **ATIN** — Adoption Taxpayer Identification Number (area 900–999, group 93)
 //!
+//! and the business **EIN** — Employer Identification Number (`XX-XXXXXXX`,
+//! IRS-assigned prefixes).
+//!
+//! Tax preparers' **PTIN**s (`PXXXXXXXX`) are supported through [`Ptin`], FATCA
+//! **GIIN**s (`XXXXXX.XXXXX.XX.XXX`) through [`Giin`], and [`IrsId`] parses any
+//! of the above. TINs issued by other countries live in [`foreign`].
+//!
+//! To find SSNs, ITINs, and ATINs embedded in free text, see [`scan`]; to
+//! mask them in streams, see [`redact`]; to rank matches by how likely they
+//! are to be real, see [`confidence`]. With the `json` feature,
+//! `redact::json` masks TINs inside JSON documents.
+//!
 //! # Example
 //!
 //! ```
-//! use tin::{Tin, Ssn, Itin, Atin};
+//! use tin::{Tin, Ssn, Itin, Atin, Ein};
 //!
 //! // Parse a specific type
 //! let ssn: Ssn = "123-45-6789".parse().unwrap();
@@ -18,30 +30,68 @@
 //! // Auto-detect type via the Tin enum
 //! let tin: Tin = "900-70-1234".parse().unwrap();
 //! assert!(matches!(tin, Tin::Itin(_)));
+//!
+//! // Only the `XX-XXXXXXX` layout selects an EIN
+//! let tin: Tin = "12-3456789".parse().unwrap();
+//! assert!(matches!(tin, Tin::Ein(_)));
 //! ```
 
 mod atin;
+mod candidates;
+pub mod confidence;
+mod convert;
+mod diagnostic;
+mod ein;
+mod explain;
+pub mod foreign;
+mod format_error;
+mod giin;
+mod irs_id;
 mod itin;
+mod last4;
+mod masked;
+mod masked_tin;
+mod options;
+mod parse;
+#[cfg(feature = "csv")]
+pub mod profile;
+mod ptin;
+pub mod redact;
+pub mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
 mod ssn;
+mod tax_id;
+mod tin_kinds;
 
 use core::fmt;
 use core::str::FromStr;
 
-use regex::Regex;
-
 pub use atin::Atin;
+pub use candidates::{Candidates, Context, Layout};
+pub use diagnostic::Diagnostic;
+pub use ein::{Campus, Ein};
+pub use explain::{Check, Rule, Trace, explain};
+pub use format_error::{CharClass, FormatError};
+pub use giin::{Giin, GiinCategory};
+pub use irs_id::IrsId;
 pub use itin::Itin;
+pub use last4::Last4;
+pub use masked::Masked;
+pub use masked_tin::MaskedTin;
+pub use options::{DashPolicy, ParseOptions, Separator};
+pub use ptin::Ptin;
 pub use ssn::Ssn;
-
-/// Matches the `XXX-XX-XXXX` or `XXXXXXXXX` format shared by SSN, ITIN, and ATIN.
-static TIN_PATTERN: &str = r"\A(?:(\d{3})-(\d{2})-(\d{4})|(\d{9}))\z";
+pub use tax_id::{TaxId, TaxIdRanges};
+pub use tin_kinds::TinKinds;
 
 /// Errors that can occur when parsing a TIN.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ParseError {
     /// The input string does not match the expected format.
-    #[error("invalid format: expected XXX-XX-XXXX or XXXXXXXXX")]
-    InvalidFormat(String),
+    #[error("invalid format: {0}")]
+    InvalidFormat(FormatError),
     /// The area number (first 3 digits) is invalid for the target type.
     #[error("invalid area number: {0}")]
     InvalidArea(u16),
@@ -51,44 +101,232 @@ pub enum ParseError {
     /// The serial number (last 4 digits) is invalid for the target type.
     #[error("invalid serial number: {0}")]
     InvalidSerial(u16),
+    /// The EIN prefix (first 2 digits) is not one the IRS assigns.
+    #[error("invalid EIN prefix: {0}")]
+    InvalidPrefix(u8),
+    /// The EIN serial number (last 7 digits) is out of range.
+    #[error("invalid EIN serial number: {0}")]
+    InvalidEinSerial(u32),
+    /// The PTIN does not start with the letter `P`.
+    #[error("invalid PTIN: expected the letter P followed by 8 digits")]
+    MissingPtinPrefix,
+    /// The PTIN number (8 digits after the `P`) is invalid.
+    #[error("invalid PTIN number: {0}")]
+    InvalidPtinNumber(u32),
+    /// The GIIN category (the 2-letter segment) is not a known code.
+    #[error("invalid GIIN category: expected LE, SL, ME, BR, or SP")]
+    InvalidGiinCategory,
+    /// The GIIN country (the last 3 digits) is not an assigned ISO 3166-1
+    /// numeric code.
+    #[error("invalid country code: {0}")]
+    InvalidCountryCode(u16),
+    /// The check digit does not match the rest of the number.
+    #[error("invalid check digit")]
+    InvalidChecksum,
+    /// No foreign TIN type is supported for the country.
+    ///
+    /// Holds the country code uppercased, or `??` if the input was not two
+    /// ASCII letters, so the error never keeps the caller's input.
+    #[error("unsupported country: {}", country_code(.0))]
+    UnsupportedCountry([u8; 2]),
+    /// The input is a valid TIN, but not of a kind the caller allows.
+    #[error("{actual} not allowed, expected {allowed}")]
+    DisallowedKind {
+        /// The kind the input parsed as.
+        actual: TinKind,
+        /// The kinds the caller allows.
+        allowed: TinKinds,
+    },
+}
+
+/// Reads a country code held by [`ParseError::UnsupportedCountry`].
+fn country_code(code: &[u8; 2]) -> &str {
+    core::str::from_utf8(code).unwrap_or("??")
+}
+
+impl ParseError {
+    /// Returns the stable code of the error, such as `TIN-E002`; see
+    /// [`ErrorKind::code`].
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+
+    /// Returns the category of the error, without its details.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            ParseError::InvalidFormat(_) => ErrorKind::InvalidFormat,
+            ParseError::InvalidArea(_) => ErrorKind::InvalidArea,
+            ParseError::InvalidGroup(_) => ErrorKind::InvalidGroup,
+            ParseError::InvalidSerial(_) => ErrorKind::InvalidSerial,
+            ParseError::InvalidPrefix(_) => ErrorKind::InvalidPrefix,
+            ParseError::InvalidEinSerial(_) => ErrorKind::InvalidEinSerial,
+            ParseError::MissingPtinPrefix => ErrorKind::MissingPtinPrefix,
+            ParseError::InvalidPtinNumber(_) => ErrorKind::InvalidPtinNumber,
+            ParseError::InvalidGiinCategory => ErrorKind::InvalidGiinCategory,
+            ParseError::InvalidCountryCode(_) => ErrorKind::InvalidCountryCode,
+            ParseError::InvalidChecksum => ErrorKind::InvalidChecksum,
+            ParseError::UnsupportedCountry(_) => ErrorKind::UnsupportedCountry,
+            ParseError::DisallowedKind { .. } => ErrorKind::DisallowedKind,
+        }
+    }
+}
+
+/// The category of a [`ParseError`], one per variant.
+///
+/// Useful for counting errors by reason without keeping the values that
+/// caused them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorKind {
+    /// See [`ParseError::InvalidFormat`].
+    InvalidFormat,
+    /// See [`ParseError::InvalidArea`].
+    InvalidArea,
+    /// See [`ParseError::InvalidGroup`].
+    InvalidGroup,
+    /// See [`ParseError::InvalidSerial`].
+    InvalidSerial,
+    /// See [`ParseError::InvalidPrefix`].
+    InvalidPrefix,
+    /// See [`ParseError::InvalidEinSerial`].
+    InvalidEinSerial,
+    /// See [`ParseError::MissingPtinPrefix`].
+    MissingPtinPrefix,
+    /// See [`ParseError::InvalidPtinNumber`].
+    InvalidPtinNumber,
+    /// See [`ParseError::InvalidGiinCategory`].
+    InvalidGiinCategory,
+    /// See [`ParseError::InvalidCountryCode`].
+    InvalidCountryCode,
+    /// See [`ParseError::InvalidChecksum`].
+    InvalidChecksum,
+    /// See [`ParseError::UnsupportedCountry`].
+    UnsupportedCountry,
+    /// See [`ParseError::DisallowedKind`].
+    DisallowedKind,
+}
+
+impl ErrorKind {
+    /// Returns a stable, machine-readable code for the error, such as
+    /// `TIN-E002` for [`ErrorKind::InvalidArea`].
+    ///
+    /// Codes are never reused or renumbered, so they are safe keys for
+    /// localized messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidFormat => "TIN-E001",
+            ErrorKind::InvalidArea => "TIN-E002",
+            ErrorKind::InvalidGroup => "TIN-E003",
+            ErrorKind::InvalidSerial => "TIN-E004",
+            ErrorKind::InvalidPrefix => "TIN-E005",
+            ErrorKind::InvalidEinSerial => "TIN-E006",
+            ErrorKind::MissingPtinPrefix => "TIN-E007",
+            ErrorKind::InvalidPtinNumber => "TIN-E008",
+            ErrorKind::InvalidGiinCategory => "TIN-E009",
+            ErrorKind::InvalidCountryCode => "TIN-E010",
+            ErrorKind::InvalidChecksum => "TIN-E011",
+            ErrorKind::UnsupportedCountry => "TIN-E012",
+            ErrorKind::DisallowedKind => "TIN-E013",
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.code()))
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::InvalidFormat => "invalid format",
+            ErrorKind::InvalidArea => "invalid area number",
+            ErrorKind::InvalidGroup => "invalid group number",
+            ErrorKind::InvalidSerial => "invalid serial number",
+            ErrorKind::InvalidPrefix => "invalid EIN prefix",
+            ErrorKind::InvalidEinSerial => "invalid EIN serial number",
+            ErrorKind::MissingPtinPrefix => "missing PTIN prefix",
+            ErrorKind::InvalidPtinNumber => "invalid PTIN number",
+            ErrorKind::InvalidGiinCategory => "invalid GIIN category",
+            ErrorKind::InvalidCountryCode => "invalid country code",
+            ErrorKind::InvalidChecksum => "invalid check digit",
+            ErrorKind::UnsupportedCountry => "unsupported country",
+            ErrorKind::DisallowedKind => "disallowed kind",
+        })
+    }
 }
 
 /// Parses a `XXX-XX-XXXX` or `XXXXXXXXX` string into `(area, group, serial)` components.
+///
+/// The input is validated in a single pass over its bytes without allocating.
 pub fn parse_components(s: &str) -> Result<(u16, u8, u16), ParseError> {
-    let re = Regex::new(TIN_PATTERN)
-        .expect("TIN_PATTERN is a valid regex: two alternates for dashed and undashed formats");
-    let caps = re
-        .captures(s)
-        .ok_or_else(|| ParseError::InvalidFormat(s.to_owned()))?;
-
-    let (area, group, serial) =
-        if let (Some(a), Some(g), Some(s)) = (caps.get(1), caps.get(2), caps.get(3)) {
-            (a.as_str(), g.as_str(), s.as_str())
-        } else if let Some(full) = caps.get(4) {
-            let full = full.as_str();
-            (&full[0..3], &full[3..5], &full[5..9])
-        } else {
-            return Err(ParseError::InvalidFormat(s.to_owned()));
-        };
+    parse_components_with(s, &ParseOptions::new())
+}
 
-    let area: u16 = area.parse().expect(
-        "area is exactly three ASCII digits as enforced by TIN_PATTERN; parse::<u16> cannot fail",
-    );
-    let group: u8 = group.parse().expect(
-        "group is exactly two ASCII digits as enforced by TIN_PATTERN; parse::<u8> cannot fail",
-    );
-    let serial: u16 = serial.parse().expect(
-        "serial is exactly four ASCII digits as enforced by TIN_PATTERN; parse::<u16> cannot fail",
-    );
-
-    Ok((area, group, serial))
+/// Like [`parse_components`], but accepting the spellings allowed by `options`.
+pub fn parse_components_with(
+    s: &str,
+    options: &ParseOptions,
+) -> Result<(u16, u8, u16), ParseError> {
+    let digits = parse::digits_with(s, parse::SSN_BREAKS, options)
+        .map_err(|position| ParseError::InvalidFormat(FormatError::at(s, position)))?;
+    Ok(components(&digits))
+}
+
+/// Splits nine digit values into `(area, group, serial)` components.
+pub(crate) fn components(digits: &[u8; parse::DIGITS]) -> (u16, u8, u16) {
+    let area = parse::fold(&digits[0..3]) as u16;
+    let group = parse::fold(&digits[3..5]) as u8;
+    let serial = parse::fold(&digits[5..9]) as u16;
+    (area, group, serial)
+}
+
+/// The kind of a [`Tin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum TinKind {
+    /// Social Security Number.
+    Ssn,
+    /// Individual Taxpayer Identification Number.
+    Itin,
+    /// Adoption Taxpayer Identification Number.
+    Atin,
+    /// Employer Identification Number.
+    Ein,
+}
+
+impl TinKind {
+    /// Returns the kind an `XXX-XX-XXXX` number would have if it were
+    /// valid, judging by its area and group alone.
+    pub(crate) fn of_components(area: u16, group: u8) -> Self {
+        match (area, group) {
+            (900.., 93) => TinKind::Atin,
+            (900.., _) => TinKind::Itin,
+            _ => TinKind::Ssn,
+        }
+    }
+}
+
+impl fmt::Display for TinKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TinKind::Ssn => "SSN",
+            TinKind::Itin => "ITIN",
+            TinKind::Atin => "ATIN",
+            TinKind::Ein => "EIN",
+        })
+    }
 }
 
 /// A U.S. Taxpayer Identification Number that auto-detects its type.
 ///
 /// The `Tin` enum wraps [`Ssn`], [`Itin`], and [`Atin`], selecting the correct
-/// variant based on the area and group numbers.
-#[derive(Clone, PartialEq, Eq, Hash)]
+/// variant based on the area and group numbers. Input in the `XX-XXXXXXX`
+/// layout is parsed as an [`Ein`]; undashed input is always read as
+/// `XXX-XX-XXXX`. Use [`Candidates`] to keep every reading of undashed input.
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
 pub enum Tin {
     /// Social Security Number.
     Ssn(Ssn),
@@ -96,43 +334,97 @@ pub enum Tin {
     Itin(Itin),
     /// Adoption Taxpayer Identification Number.
     Atin(Atin),
+    /// Employer Identification Number.
+    Ein(Ein),
 }
 
 impl Tin {
     /// Returns the area number (first 3 digits).
+    ///
+    /// For an EIN this is the first 3 of its nine digits.
     pub fn area(&self) -> u16 {
-        match self {
-            Tin::Ssn(v) => v.area(),
-            Tin::Itin(v) => v.area(),
-            Tin::Atin(v) => v.area(),
-        }
+        TaxId::area(self)
     }
 
     /// Returns the group number (middle 2 digits).
+    ///
+    /// For an EIN this is the 4th and 5th of its nine digits.
     pub fn group(&self) -> u8 {
-        match self {
-            Tin::Ssn(v) => v.group(),
-            Tin::Itin(v) => v.group(),
-            Tin::Atin(v) => v.group(),
-        }
+        TaxId::group(self)
     }
 
     /// Returns the serial number (last 4 digits).
+    ///
+    /// For an EIN this is the last 4 of its nine digits.
     pub fn serial(&self) -> u16 {
-        match self {
-            Tin::Ssn(v) => v.serial(),
-            Tin::Itin(v) => v.serial(),
-            Tin::Atin(v) => v.serial(),
-        }
+        TaxId::serial(self)
     }
-}
 
-impl FromStr for Tin {
-    type Err = ParseError;
+    /// Returns the kind of this TIN.
+    pub fn kind(&self) -> TinKind {
+        TaxId::kind(self)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (area, group, serial) = parse_components(s)?;
+    /// Returns a wrapper whose `Display` masks all but the last 4 digits.
+    pub fn masked(&self) -> Masked<Self> {
+        Masked::new(self.clone())
+    }
+
+    /// Parses a TIN, accepting the spellings allowed by `options`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        match parse_components_with(s, options) {
+            Ok((area, group, serial)) => Self::from_components(area, group, serial),
+            // Not `XXX-XX-XXXX` or `XXXXXXXXX`, so only the EIN layout remains.
+            Err(ssn) => match Ein::parse_with(s, options) {
+                Ok(ein) => Ok(Tin::Ein(ein)),
+                // Report whichever layout matched more of the input.
+                Err(ParseError::InvalidFormat(ein)) => match ssn {
+                    ParseError::InvalidFormat(ssn) if ssn.position() > ein.position() => {
+                        Err(ParseError::InvalidFormat(ssn))
+                    }
+                    _ => Err(ParseError::InvalidFormat(ein)),
+                },
+                Err(err) => Err(err),
+            },
+        }
+    }
+
+    /// Parses a TIN, rejecting kinds not in `allowed`.
+    ///
+    /// Undashed input is read as an EIN when that is allowed and the
+    /// SSN-family reading is not, as [`Candidates`] would. A valid TIN of
+    /// another kind fails with [`ParseError::DisallowedKind`], which names
+    /// the kind the input actually was.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tin::{ParseError, Tin, TinKind, TinKinds};
+    ///
+    /// let individual = TinKinds::SSN | TinKinds::ITIN;
+    /// assert!(Tin::parse_as("900-70-1234", individual).is_ok());
+    /// assert_eq!(
+    ///     Tin::parse_as("900-93-1234", individual),
+    ///     Err(ParseError::DisallowedKind {
+    ///         actual: TinKind::Atin,
+    ///         allowed: individual,
+    ///     })
+    /// );
+    /// assert_eq!(Tin::parse_as("123456789", TinKinds::EIN).unwrap().kind(), TinKind::Ein);
+    /// ```
+    pub fn parse_as(s: &str, allowed: TinKinds) -> Result<Self, ParseError> {
+        let candidates: Candidates = s.parse()?;
+        match candidates.iter().find(|tin| allowed.contains(tin.kind())) {
+            Some(tin) => Ok(tin.clone()),
+            None => Err(ParseError::DisallowedKind {
+                actual: candidates.best().kind(),
+                allowed,
+            }),
+        }
+    }
 
+    /// Selects and validates the SSN, ITIN, or ATIN reading of the components.
+    pub(crate) fn from_components(area: u16, group: u8, serial: u16) -> Result<Self, ParseError> {
         match area {
             // SSN range: 001-665, 667-899
             1..=665 | 667..=899 => Ok(Tin::Ssn(Ssn::new(area, group, serial)?)),
@@ -141,9 +433,9 @@ impl FromStr for Tin {
             900..=999 if itin::is_valid_itin_group(group) => {
                 Ok(Tin::Itin(Itin::new(area, group, serial)?))
             }
-            // Invalid: area 0, 666, or 900-999 with invalid group
+            // Invalid: area 0, 666, or above 999, or 900-999 with invalid group
             _ => {
-                if area == 0 || area == 666 {
+                if area == 0 || area == 666 || area > 999 {
                     Err(ParseError::InvalidArea(area))
                 } else {
                     Err(ParseError::InvalidGroup(group))
@@ -153,12 +445,95 @@ impl FromStr for Tin {
     }
 }
 
+impl TaxId for Tin {
+    /// Returns `(area, group, serial)`, splitting an EIN's nine digits the
+    /// same way.
+    fn components(&self) -> (u16, u8, u16) {
+        match self {
+            Tin::Ssn(v) => v.components(),
+            Tin::Itin(v) => v.components(),
+            Tin::Atin(v) => v.components(),
+            Tin::Ein(v) => {
+                let number = v.number();
+                (
+                    (number / 1_000_000) as u16,
+                    (number / 10_000 % 100) as u8,
+                    (number % 10_000) as u16,
+                )
+            }
+        }
+    }
+
+    fn kind(&self) -> TinKind {
+        match self {
+            Tin::Ssn(_) => TinKind::Ssn,
+            Tin::Itin(_) => TinKind::Itin,
+            Tin::Atin(_) => TinKind::Atin,
+            Tin::Ein(_) => TinKind::Ein,
+        }
+    }
+
+    fn fmt_masked(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tin::Ssn(v) => v.fmt_masked(f),
+            Tin::Itin(v) => v.fmt_masked(f),
+            Tin::Atin(v) => v.fmt_masked(f),
+            Tin::Ein(v) => v.fmt_masked(f),
+        }
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Tin {
+    /// Returns the nine digits as a single number.
+    fn number(&self) -> u32 {
+        u32::from(self.area()) * 1_000_000
+            + u32::from(self.group()) * 10_000
+            + u32::from(self.serial())
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Tin {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        (self.kind() as u8).ct_eq(&(other.kind() as u8)) & self.number().ct_eq(&other.number())
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Tin {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Tin {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Tin {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the kind and digits.
+        state.write_u32(self.number() | (self.kind() as u32) << 30);
+    }
+}
+
+impl FromStr for Tin {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &ParseOptions::new())
+    }
+}
+
 impl fmt::Display for Tin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Tin::Ssn(v) => v.fmt(f),
             Tin::Itin(v) => v.fmt(f),
             Tin::Atin(v) => v.fmt(f),
+            Tin::Ein(v) => v.fmt(f),
         }
     }
 }
@@ -169,6 +544,7 @@ impl fmt::Debug for Tin {
             Tin::Ssn(v) => v.fmt(f),
             Tin::Itin(v) => v.fmt(f),
             Tin::Atin(v) => v.fmt(f),
+            Tin::Ein(v) => v.fmt(f),
         }
     }
 }
@@ -230,6 +606,48 @@ mod tests {
         assert!(matches!(tin, Tin::Atin(_)));
     }
 
+    #[test]
+    fn tin_detects_ein() {
+        let tin: Tin = "12-3456789".parse().unwrap();
+        assert!(matches!(tin, Tin::Ein(_)));
+        assert_eq!((tin.area(), tin.group(), tin.serial()), (123, 45, 6789));
+        assert_eq!(tin.to_string(), "12-3456789");
+        assert_eq!(format!("{tin:?}"), "Ein(XX-XXX6789)");
+    }
+
+    #[test]
+    fn tin_undashed_prefers_ssn_family() {
+        let tin: Tin = "123456789".parse().unwrap();
+        assert!(matches!(tin, Tin::Ssn(_)));
+    }
+
+    #[test]
+    fn tin_invalid_ein_prefix() {
+        assert!(matches!(
+            "07-1234567".parse::<Tin>(),
+            Err(ParseError::InvalidPrefix(7))
+        ));
+        assert!(matches!(
+            "1-23456789".parse::<Tin>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn tin_parse_with_options() {
+        let options = ParseOptions::lenient();
+        let tin = Tin::parse_with(" 900 93 1234 ", &options).unwrap();
+        assert!(matches!(tin, Tin::Atin(_)));
+        let tin = Tin::parse_with("12 3456789", &options).unwrap();
+        assert!(matches!(tin, Tin::Ein(_)));
+
+        let options = ParseOptions::new().dashes(DashPolicy::Forbidden);
+        assert!(matches!(
+            Tin::parse_with("12-3456789", &options),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn tin_invalid_area_000() {
         assert!(matches!(
@@ -255,6 +673,45 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn tin_kind() {
+        let kinds: Vec<TinKind> = ["123-45-6789", "900-70-1234", "900-93-1234", "12-3456789"]
+            .iter()
+            .map(|s| s.parse::<Tin>().unwrap().kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            [TinKind::Ssn, TinKind::Itin, TinKind::Atin, TinKind::Ein]
+        );
+        assert_eq!(TinKind::Itin.to_string(), "ITIN");
+    }
+
+    #[test]
+    fn tin_parse_as_filters_kinds() {
+        let w2 = TinKinds::SSN;
+        assert!(Tin::parse_as("123-45-6789", w2).is_ok());
+        let err = Tin::parse_as("900-70-1234", w2).unwrap_err();
+        assert_eq!(err.to_string(), "ITIN not allowed, expected SSN");
+        assert_eq!(err.code(), "TIN-E013");
+        // Errors other than the kind are reported as `Tin` reports them.
+        assert_eq!(
+            Tin::parse_as("900-10-1234", w2),
+            Err(ParseError::InvalidGroup(10))
+        );
+        // Undashed input falls back to the EIN reading only when allowed.
+        assert_eq!(
+            Tin::parse_as("123456789", TinKinds::EIN).unwrap().kind(),
+            TinKind::Ein
+        );
+        assert_eq!(
+            Tin::parse_as("12-3456789", TinKinds::INDIVIDUAL),
+            Err(ParseError::DisallowedKind {
+                actual: TinKind::Ein,
+                allowed: TinKinds::INDIVIDUAL,
+            })
+        );
+    }
+
     #[test]
     fn tin_display_delegates() {
         let tin: Tin = "123-45-6789".parse().unwrap();
@@ -273,6 +730,29 @@ mod tests {
         assert_eq!(format!("{tin:?}"), "Atin(XXX-XX-5678)");
     }
 
+    #[test]
+    fn tin_round_trips_every_kind() {
+        for (input, kind, masked) in [
+            ("123-45-6789", TinKind::Ssn, "XXX-XX-6789"),
+            ("900-70-1234", TinKind::Itin, "XXX-XX-1234"),
+            ("900-93-5678", TinKind::Atin, "XXX-XX-5678"),
+            ("66-6123456", TinKind::Ein, "XX-XXX3456"),
+            ("12-3006789", TinKind::Ein, "XX-XXX6789"),
+        ] {
+            let tin: Tin = input.parse().unwrap();
+            assert_eq!(tin.kind(), kind, "{input}");
+            assert_eq!(tin.to_string(), input);
+            assert_eq!(tin.to_string().parse::<Tin>().as_ref(), Ok(&tin));
+            assert_eq!(tin.masked().to_string(), masked);
+        }
+
+        // An EIN's digits need not be a valid area and group.
+        let tin: Tin = "66-6123456".parse().unwrap();
+        assert_eq!((tin.area(), tin.group(), tin.serial()), (666, 12, 3456));
+        let tin: Tin = "12-3006789".parse().unwrap();
+        assert_eq!((tin.area(), tin.group(), tin.serial()), (123, 0, 6789));
+    }
+
     #[test]
     fn tin_ssn_boundary_667() {
         let tin: Tin = "667-01-0001".parse().unwrap();
@@ -284,4 +764,48 @@ mod tests {
         let tin: Tin = "899-99-9999".parse().unwrap();
         assert!(matches!(tin, Tin::Ssn(_)));
     }
+
+    #[test]
+    fn error_kind() {
+        assert_eq!(
+            "000-12-3456".parse::<Tin>().unwrap_err().kind(),
+            ErrorKind::InvalidArea
+        );
+        assert_eq!(
+            "12a".parse::<Tin>().unwrap_err().kind(),
+            ErrorKind::InvalidFormat
+        );
+        assert_eq!(ErrorKind::InvalidGroup.to_string(), "invalid group number");
+        assert_eq!(ErrorKind::InvalidFormat.code(), "TIN-E001");
+        assert_eq!(ParseError::InvalidChecksum.code(), "TIN-E011");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_tin_eq_and_hash() {
+        use std::hash::BuildHasher;
+
+        let ssn: Tin = "123-45-6789".parse().unwrap();
+        let ein: Tin = "12-3456789".parse().unwrap();
+        assert_eq!(ssn, "123456789".parse().unwrap());
+        assert_ne!(ssn, ein);
+        let state = std::hash::RandomState::new();
+        assert_ne!(state.hash_one(&ssn), state.hash_one(&ein));
+    }
+
+    #[test]
+    fn format_error_keeps_position_not_input() {
+        let err = "123-45-678".parse::<Tin>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid format: unexpected end of input at byte 10"
+        );
+
+        // The EIN reading gets further than the SSN reading, so it is reported.
+        let Err(ParseError::InvalidFormat(err)) = "12-34567x9".parse::<Tin>() else {
+            panic!("expected a format error");
+        };
+        assert_eq!((err.position(), err.class()), (8, CharClass::Letter));
+        assert!(!format!("{err:?}").contains("1234"));
+    }
 }
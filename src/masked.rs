@@ -0,0 +1,97 @@
+//! A wrapper that keeps a TIN masked when displayed.
+
+use core::fmt;
+
+use crate::{Ein, TaxId};
+
+/// A TIN whose `Display` masks all but the last 4 digits.
+///
+/// `Debug` already masks, but `Display` prints the full number, so a stray
+/// `format!("{ssn}")` in a log line leaks it. Holding a `Masked<Ssn>` instead
+/// makes the masked form the default, and reading the full number takes an
+/// explicit [`expose`](Masked::expose) or [`into_exposed`](Masked::into_exposed)
+/// call that is easy to find in review.
+///
+/// # Example
+///
+/// ```
+/// use tin::{Masked, Ssn};
+///
+/// let ssn: Masked<Ssn> = Masked::new("123-45-6789".parse().unwrap());
+/// assert_eq!(format!("{ssn}"), "XXX-XX-6789");
+/// assert_eq!(format!("{ssn:?}"), "Ssn(XXX-XX-6789)");
+/// assert_eq!(ssn.expose().to_string(), "123-45-6789");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Masked<T>(T);
+
+impl<T> Masked<T> {
+    /// Wraps `value`.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the wrapped value, whose `Display` shows the full number.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the value, whose `Display` shows the full number.
+    pub fn into_exposed(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Masked<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Masked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: TaxId> fmt::Display for Masked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_masked(f)
+    }
+}
+
+impl fmt::Display for Masked<Ein> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_masked(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Atin, Itin, Ssn, Tin};
+
+    #[test]
+    fn display_masks_every_type() {
+        let ssn: Ssn = "123-45-6789".parse().unwrap();
+        let itin: Itin = "900-70-1234".parse().unwrap();
+        let atin: Atin = "900-93-5678".parse().unwrap();
+        let ein: Ein = "12-3456789".parse().unwrap();
+        assert_eq!(ssn.masked().to_string(), "XXX-XX-6789");
+        assert_eq!(itin.masked().to_string(), "XXX-XX-1234");
+        assert_eq!(atin.masked().to_string(), "XXX-XX-5678");
+        assert_eq!(ein.masked().to_string(), "XX-XXX6789");
+
+        let tin: Tin = "900-70-1234".parse().unwrap();
+        assert_eq!(tin.masked().to_string(), "XXX-XX-1234");
+        assert_eq!(format!("{:?}", tin.masked()), "Itin(XXX-XX-1234)");
+    }
+
+    #[test]
+    fn expose_reveals_the_number() {
+        let tin: Masked<Tin> = "12-3456789".parse::<Tin>().unwrap().into();
+        assert_eq!(tin.expose().to_string(), "12-3456789");
+        assert_eq!(tin.clone().into_exposed().to_string(), "12-3456789");
+        assert_eq!(tin, Tin::Ein("12-3456789".parse().unwrap()).masked());
+    }
+}
@@ -0,0 +1,225 @@
+//! TINs known only in masked form.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Atin, Ein, FormatError, Itin, Last4, Layout, ParseError, Ssn, Tin};
+
+/// Characters accepted in place of a hidden digit.
+const MASK_CHARS: &[u8] = b"Xx*#";
+
+/// A TIN of which only the last four digits are known, such as
+/// `XXX-XX-1234`.
+///
+/// Parses the `XXX-XX-XXXX`, `XX-XXXXXXX`, and undashed layouts with the
+/// hidden digits written as `X`, `x`, `*`, or `#`, one character throughout.
+/// Formats the way `Debug` masks a full number: `XXX-XX-1234`, `XX-XXX1234`,
+/// or `XXXXX1234`.
+///
+/// # Example
+///
+/// ```
+/// use tin::{MaskedTin, Tin};
+///
+/// let masked: MaskedTin = "***-**-6789".parse().unwrap();
+/// assert_eq!(masked.to_string(), "XXX-XX-6789");
+///
+/// let tin: Tin = "123-45-6789".parse().unwrap();
+/// assert!(masked.matches(&tin));
+/// // The layout implies an SSN, ITIN, or ATIN, so an EIN never matches.
+/// assert!(!masked.matches(&"12-3456789".parse::<Tin>().unwrap()));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaskedTin {
+    layout: Layout,
+    last4: Last4,
+}
+
+impl MaskedTin {
+    /// Returns the layout the masked value was written in.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the last four digits.
+    pub fn last4(&self) -> Last4 {
+        self.last4
+    }
+
+    /// Returns `true` if `tin` could be the masked number: its layout fits
+    /// and it ends in the same four digits.
+    ///
+    /// The digits are compared as [`Last4::matches`] does, in constant time
+    /// with the `hardened` feature.
+    pub fn matches(&self, tin: impl Into<MaskedTin>) -> bool {
+        let tin = tin.into();
+        let layout_fits = self.layout == Layout::Undashed || self.layout == tin.layout;
+        self.last4.matches(tin.last4) & layout_fits
+    }
+}
+
+impl FromStr for MaskedTin {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        let (layout, template): (_, &[u8]) = match (bytes.get(2), bytes.get(3)) {
+            (Some(b'-'), _) => (Layout::Ein, b"MM-MMM9999"),
+            (_, Some(b'-')) => (Layout::Ssn, b"MMM-MM-9999"),
+            _ => (Layout::Undashed, b"MMMMM9999"),
+        };
+        let mask = bytes.first().copied().filter(|b| MASK_CHARS.contains(b));
+        let mismatch = template
+            .iter()
+            .enumerate()
+            .position(|(i, &expected)| {
+                let Some(&b) = bytes.get(i) else {
+                    return true;
+                };
+                match expected {
+                    b'M' => Some(b) != mask,
+                    b'9' => !b.is_ascii_digit(),
+                    _ => b != expected,
+                }
+            })
+            .or((bytes.len() > template.len()).then_some(template.len()));
+        if let Some(position) = mismatch {
+            return Err(ParseError::InvalidFormat(FormatError::at(s, position)));
+        }
+        let last4 = s[s.len() - 4..].parse()?;
+        Ok(Self { layout, last4 })
+    }
+}
+
+impl From<&Ssn> for MaskedTin {
+    fn from(ssn: &Ssn) -> Self {
+        Self {
+            layout: Layout::Ssn,
+            last4: ssn.into(),
+        }
+    }
+}
+
+impl From<&Itin> for MaskedTin {
+    fn from(itin: &Itin) -> Self {
+        Self {
+            layout: Layout::Ssn,
+            last4: itin.into(),
+        }
+    }
+}
+
+impl From<&Atin> for MaskedTin {
+    fn from(atin: &Atin) -> Self {
+        Self {
+            layout: Layout::Ssn,
+            last4: atin.into(),
+        }
+    }
+}
+
+impl From<&Ein> for MaskedTin {
+    fn from(ein: &Ein) -> Self {
+        Self {
+            layout: Layout::Ein,
+            last4: ein.into(),
+        }
+    }
+}
+
+impl From<&Tin> for MaskedTin {
+    fn from(tin: &Tin) -> Self {
+        match tin {
+            Tin::Ssn(v) => v.into(),
+            Tin::Itin(v) => v.into(),
+            Tin::Atin(v) => v.into(),
+            Tin::Ein(v) => v.into(),
+        }
+    }
+}
+
+impl fmt::Display for MaskedTin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hidden = match self.layout {
+            Layout::Ssn => "XXX-XX-",
+            Layout::Ein => "XX-XXX",
+            Layout::Undashed => "XXXXX",
+        };
+        write!(f, "{hidden}{}", self.last4)
+    }
+}
+
+impl fmt::Debug for MaskedTin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MaskedTin({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mask_notations() {
+        for (input, normalized) in [
+            ("XXX-XX-1234", "XXX-XX-1234"),
+            ("xxx-xx-1234", "XXX-XX-1234"),
+            ("***-**-1234", "XXX-XX-1234"),
+            ("###-##-1234", "XXX-XX-1234"),
+            ("**-***1234", "XX-XXX1234"),
+            ("XXXXX1234", "XXXXX1234"),
+        ] {
+            assert_eq!(
+                input.parse::<MaskedTin>().unwrap().to_string(),
+                normalized,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_mixed_or_misplaced_masks() {
+        for (input, position) in [
+            ("XX*-XX-1234", 2),
+            ("XXX-XX-12345", 11),
+            ("XXX-XX-123", 10),
+            ("123-45-6789", 0),
+            ("XXX-XX-X234", 7),
+            ("", 0),
+        ] {
+            assert!(
+                matches!(
+                    input.parse::<MaskedTin>(),
+                    Err(ParseError::InvalidFormat(err)) if err.position() == position
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn formats_like_debug() {
+        let ssn: Ssn = "123-45-6789".parse().unwrap();
+        let ein: Ein = "12-3456789".parse().unwrap();
+        assert_eq!(
+            format!("{ssn:?}"),
+            format!("Ssn({})", MaskedTin::from(&ssn))
+        );
+        assert_eq!(
+            format!("{ein:?}"),
+            format!("Ein({})", MaskedTin::from(&ein))
+        );
+    }
+
+    #[test]
+    fn matching_respects_layout() {
+        let itin: Tin = "900-70-1234".parse().unwrap();
+        let ein: Tin = "12-3451234".parse().unwrap();
+        let dashed: MaskedTin = "XXX-XX-1234".parse().unwrap();
+        let undashed: MaskedTin = "XXXXX1234".parse().unwrap();
+        assert!(dashed.matches(&itin));
+        assert!(!dashed.matches(&ein));
+        assert!(undashed.matches(&itin) && undashed.matches(&ein));
+        assert!(!undashed.matches(&"900-70-1235".parse::<Itin>().unwrap()));
+    }
+}
@@ -0,0 +1,179 @@
+//! Options for lenient parsing.
+
+/// A separator character that may appear between digit groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Separator {
+    /// `-`, as in `123-45-6789`.
+    Dash,
+    /// ` `, as in `123 45 6789`.
+    Space,
+    /// `.`, as in `123.45.6789`.
+    Dot,
+}
+
+impl Separator {
+    const fn bit(self) -> u8 {
+        match self {
+            Separator::Dash => 1,
+            Separator::Space => 2,
+            Separator::Dot => 4,
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            b'-' => Some(Separator::Dash),
+            b' ' => Some(Separator::Space),
+            b'.' => Some(Separator::Dot),
+            _ => None,
+        }
+    }
+}
+
+/// Whether digit groups must, may, or must not be separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DashPolicy {
+    /// Accept `123-45-6789` and `123456789`.
+    Optional,
+    /// Accept only `123-45-6789`.
+    Required,
+    /// Accept only `123456789`.
+    Forbidden,
+}
+
+/// Controls which spellings of a TIN the parser accepts.
+///
+/// The default is strict: only `XXX-XX-XXXX` or `XXXXXXXXX`, with no
+/// surrounding whitespace, exactly as [`FromStr`](core::str::FromStr) accepts.
+///
+/// # Example
+///
+/// ```
+/// use tin::{DashPolicy, ParseOptions, Separator, Ssn};
+///
+/// let options = ParseOptions::new()
+///     .separator(Separator::Space)
+///     .separator(Separator::Dot)
+///     .trim(true);
+/// let ssn = Ssn::parse_with(" 123.45.6789 ", &options).unwrap();
+/// assert_eq!(ssn.to_string(), "123-45-6789");
+///
+/// let options = ParseOptions::new().dashes(DashPolicy::Required);
+/// assert!(Ssn::parse_with("123456789", &options).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    separators: u8,
+    trim: bool,
+    dashes: DashPolicy,
+    mixed_separators: bool,
+}
+
+impl ParseOptions {
+    /// Returns the strict options: dashes only, optional, no trimming.
+    pub const fn new() -> Self {
+        Self {
+            separators: Separator::Dash.bit(),
+            trim: false,
+            dashes: DashPolicy::Optional,
+            mixed_separators: false,
+        }
+    }
+
+    /// Returns options accepting any separator, mixed separators, and
+    /// surrounding whitespace.
+    pub const fn lenient() -> Self {
+        Self {
+            separators: Separator::Dash.bit() | Separator::Space.bit() | Separator::Dot.bit(),
+            trim: true,
+            dashes: DashPolicy::Optional,
+            mixed_separators: true,
+        }
+    }
+
+    /// Also accepts the given separator between digit groups.
+    pub const fn separator(mut self, separator: Separator) -> Self {
+        self.separators |= separator.bit();
+        self
+    }
+
+    /// Accepts only the given separators between digit groups.
+    pub fn separators(mut self, separators: &[Separator]) -> Self {
+        self.separators = separators.iter().fold(0, |acc, s| acc | s.bit());
+        self
+    }
+
+    /// Sets whether leading and trailing whitespace is ignored.
+    pub const fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets whether digit groups must, may, or must not be separated.
+    pub const fn dashes(mut self, policy: DashPolicy) -> Self {
+        self.dashes = policy;
+        self
+    }
+
+    /// Sets whether different separators may appear in one input, as in
+    /// `123-45 6789`.
+    pub const fn mixed_separators(mut self, mixed: bool) -> Self {
+        self.mixed_separators = mixed;
+        self
+    }
+
+    /// Returns the separator `b` stands for, if it is allowed.
+    pub(crate) fn allowed_separator(&self, b: u8) -> Option<Separator> {
+        Separator::from_byte(b).filter(|s| self.separators & s.bit() != 0)
+    }
+
+    pub(crate) fn trims(&self) -> bool {
+        self.trim
+    }
+
+    pub(crate) fn dash_policy(&self) -> DashPolicy {
+        self.dashes
+    }
+
+    pub(crate) fn allows_mixed(&self) -> bool {
+        self.mixed_separators
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_allows_only_dash() {
+        let options = ParseOptions::new();
+        assert_eq!(options.allowed_separator(b'-'), Some(Separator::Dash));
+        assert_eq!(options.allowed_separator(b' '), None);
+        assert_eq!(options.allowed_separator(b'.'), None);
+        assert!(!options.trims());
+        assert_eq!(options, ParseOptions::default());
+    }
+
+    #[test]
+    fn separators_replace_the_set() {
+        let options = ParseOptions::new().separators(&[Separator::Dot]);
+        assert_eq!(options.allowed_separator(b'-'), None);
+        assert_eq!(options.allowed_separator(b'.'), Some(Separator::Dot));
+    }
+
+    #[test]
+    fn lenient_allows_everything() {
+        let options = ParseOptions::lenient();
+        for b in [b'-', b' ', b'.'] {
+            assert!(options.allowed_separator(b).is_some());
+        }
+        assert!(options.trims());
+        assert!(options.allows_mixed());
+    }
+}
@@ -0,0 +1,245 @@
+//! Byte-level parser for nine-digit identifiers such as `XXX-XX-XXXX`.
+//!
+//! The parser walks the input once, collecting digits into a fixed array and
+//! tracking which separator layout the input has committed to. It never
+//! allocates and never backtracks.
+
+use crate::{DashPolicy, ParseOptions};
+
+/// Number of digits in an SSN, ITIN, ATIN, or EIN.
+pub(crate) const DIGITS: usize = 9;
+
+/// Digit counts after which a dash appears in `XXX-XX-XXXX`.
+pub(crate) const SSN_BREAKS: &[usize] = &[3, 5];
+
+/// Digit counts after which a dash appears in `XX-XXXXXXX`.
+pub(crate) const EIN_BREAKS: &[usize] = &[2];
+
+/// Separator layout, decided by the byte that follows the area number.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// No byte seen after the first digit group yet.
+    Undecided,
+    /// A separator after every digit group.
+    Dashed,
+    /// No separators at all.
+    Undashed,
+}
+
+/// Parser state between bytes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Expecting a digit.
+    Digit,
+    /// Just consumed a separator; a digit must follow.
+    AfterSeparator,
+}
+
+/// Parses the input into its nine digit values (0–9), or the byte offset
+/// where it stops being nine bare digits or nine digits dashed after each
+/// count in `breaks`.
+pub(crate) fn digits(s: &str, breaks: &[usize]) -> Result<[u8; DIGITS], usize> {
+    digits_with(s, breaks, &ParseOptions::new())
+}
+
+/// Like [`digits`], but accepting the spellings allowed by `options`.
+///
+/// Offsets are into `s` itself, including any whitespace that is trimmed.
+pub(crate) fn digits_with(
+    s: &str,
+    breaks: &[usize],
+    options: &ParseOptions,
+) -> Result<[u8; DIGITS], usize> {
+    let (offset, s) = if options.trims() {
+        let start = s.trim_start();
+        (s.len() - start.len(), start.trim_end())
+    } else {
+        (0, s)
+    };
+    let first = breaks[0];
+    let mut out = [0u8; DIGITS];
+    let mut count = 0;
+    let mut layout = Layout::Undecided;
+    let mut state = State::Digit;
+    let mut separator = None;
+    // Where a separator would have gone, once the input skips it.
+    let mut undashed_at = None;
+
+    for (i, &b) in s.as_bytes().iter().enumerate() {
+        let position = offset + i;
+        match b {
+            b'0'..=b'9' => {
+                if count == DIGITS {
+                    return Err(position);
+                }
+                if layout == Layout::Undecided && count == first {
+                    layout = Layout::Undashed;
+                    undashed_at = Some(position);
+                }
+                if layout == Layout::Dashed && state == State::Digit && breaks.contains(&count) {
+                    return Err(position);
+                }
+                out[count] = b - b'0';
+                count += 1;
+                state = State::Digit;
+            }
+            _ if options.allowed_separator(b).is_some() => {
+                if state == State::AfterSeparator || options.dash_policy() == DashPolicy::Forbidden
+                {
+                    return Err(position);
+                }
+                match separator {
+                    None => separator = Some(b),
+                    Some(prev) if prev != b && !options.allows_mixed() => return Err(position),
+                    Some(_) => {}
+                }
+                match layout {
+                    Layout::Undecided if count == first => layout = Layout::Dashed,
+                    Layout::Dashed if breaks.contains(&count) => {}
+                    _ => return Err(position),
+                }
+                state = State::AfterSeparator;
+            }
+            _ => return Err(position),
+        }
+    }
+
+    if count != DIGITS || state != State::Digit {
+        return Err(offset + s.len());
+    }
+    if options.dash_policy() == DashPolicy::Required && layout != Layout::Dashed {
+        return Err(undashed_at.unwrap_or(offset));
+    }
+    Ok(out)
+}
+
+/// Folds a slice of digit values into an integer.
+pub(crate) fn fold(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + u32::from(d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Separator;
+
+    #[test]
+    fn accepts_dashed_and_undashed() {
+        let expected = Ok([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(digits("123-45-6789", SSN_BREAKS), expected);
+        assert_eq!(digits("123456789", SSN_BREAKS), expected);
+    }
+
+    #[test]
+    fn rejects_misplaced_or_partial_dashes() {
+        for (input, position) in [
+            ("123-456789", 6),
+            ("12345-6789", 5),
+            ("12-345-6789", 2),
+            ("123--45-6789", 4),
+            ("123-45-678-9", 10),
+            ("-123456789", 0),
+            ("123456789-", 9),
+            ("123-45-6789-", 11),
+            ("1234-5-6789", 4),
+        ] {
+            assert_eq!(digits(input, SSN_BREAKS), Err(position), "{input}");
+        }
+    }
+
+    #[test]
+    fn ein_layout() {
+        let expected = Ok([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(digits("12-3456789", EIN_BREAKS), expected);
+        assert_eq!(digits("123456789", EIN_BREAKS), expected);
+        assert_eq!(digits("123-45-6789", EIN_BREAKS), Err(3));
+        assert_eq!(digits("12-3456789", SSN_BREAKS), Err(2));
+        assert_eq!(digits("12-345-6789", EIN_BREAKS), Err(6));
+    }
+
+    #[test]
+    fn options_separators_and_trim() {
+        let expected = Ok([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let options = ParseOptions::lenient();
+        for input in [
+            "123 45 6789",
+            "123.45.6789",
+            " 123-45-6789 ",
+            "123-45 6789",
+            "\t123456789\n",
+        ] {
+            assert_eq!(
+                digits_with(input, SSN_BREAKS, &options),
+                expected,
+                "{input:?}"
+            );
+        }
+        assert_eq!(digits_with("12 3456789", EIN_BREAKS, &options), expected);
+        assert_eq!(digits_with("123  45 6789", SSN_BREAKS, &options), Err(4));
+        assert_eq!(digits_with("1 23456789", SSN_BREAKS, &options), Err(1));
+        assert_eq!(digits_with("  123-45-678 ", SSN_BREAKS, &options), Err(12));
+    }
+
+    #[test]
+    fn options_reject_mixed_unless_allowed() {
+        let options = ParseOptions::new().separator(Separator::Space);
+        assert!(digits_with("123 45 6789", SSN_BREAKS, &options).is_ok());
+        assert_eq!(digits_with("123-45 6789", SSN_BREAKS, &options), Err(6));
+        let options = options.mixed_separators(true);
+        assert!(digits_with("123-45 6789", SSN_BREAKS, &options).is_ok());
+    }
+
+    #[test]
+    fn options_dash_policy() {
+        let required = ParseOptions::new().dashes(DashPolicy::Required);
+        assert!(digits_with("123-45-6789", SSN_BREAKS, &required).is_ok());
+        assert_eq!(digits_with("123456789", SSN_BREAKS, &required), Err(3));
+
+        let forbidden = ParseOptions::new().dashes(DashPolicy::Forbidden);
+        assert!(digits_with("123456789", SSN_BREAKS, &forbidden).is_ok());
+        assert_eq!(digits_with("123-45-6789", SSN_BREAKS, &forbidden), Err(3));
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        for (input, position) in [
+            ("", 0),
+            ("12345678", 8),
+            ("1234567890", 9),
+            ("123-45-678", 10),
+            ("123-45-67890", 11),
+        ] {
+            assert_eq!(digits(input, SSN_BREAKS), Err(position), "{input}");
+        }
+    }
+
+    #[test]
+    fn rejects_non_ascii_digits() {
+        // U+0663 ARABIC-INDIC DIGIT THREE is `\d` under Unicode but not accepted here.
+        assert_eq!(digits("12\u{663}-45-6789", SSN_BREAKS), Err(2));
+        assert_eq!(digits("123 45 6789", SSN_BREAKS), Err(3));
+        assert_eq!(digits("123-45-6789\n", SSN_BREAKS), Err(11));
+    }
+
+    #[test]
+    fn agrees_with_reference_regex() {
+        let re = regex::Regex::new(r"\A(?:(\d{3})-(\d{2})-(\d{4})|(\d{9}))\z").unwrap();
+        let alphabet = [b'1', b'-', b'x'];
+        let mut buf = Vec::new();
+        for len in 0..=11u32 {
+            for mut n in 0..alphabet.len().pow(len) {
+                buf.clear();
+                for _ in 0..len {
+                    buf.push(alphabet[n % alphabet.len()]);
+                    n /= alphabet.len();
+                }
+                let input = core::str::from_utf8(&buf).unwrap();
+                assert_eq!(
+                    digits(input, SSN_BREAKS).is_ok(),
+                    re.is_match(input),
+                    "{input}"
+                );
+            }
+        }
+    }
+}
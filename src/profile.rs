@@ -0,0 +1,370 @@
+//! Inferring which CSV columns hold TINs.
+//!
+//! [`Profiler`] streams a CSV file and tallies, for every column, how many
+//! values parse as each kind of [`Tin`], how they are punctuated, and why the
+//! rest fail. Only counts are kept, never the values themselves, so memory
+//! depends on the number of columns rather than rows.
+//!
+//! # Example
+//!
+//! ```
+//! use tin::TinKind;
+//! use tin::profile::{Profiler, Verdict};
+//!
+//! let data = "name,id,amount\nAda,123-45-6789,10\nBob,900701234,20\n";
+//! let columns = Profiler::new().profile(data.as_bytes())?;
+//! assert_eq!(columns[0].verdict(), Verdict::NotTin);
+//! assert_eq!(columns[1].name(), Some("id"));
+//! assert_eq!(columns[1].verdict(), Verdict::Tin);
+//! assert_eq!(columns[1].fraction(TinKind::Itin), 0.5);
+//! # Ok::<(), csv::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::{ErrorKind, Layout, Tin, TinKind};
+
+/// Share of non-empty values that must be valid TINs for [`Verdict::Tin`].
+const TIN_THRESHOLD: f64 = 0.9;
+
+/// Share of non-empty values that must be valid TINs, or at least nine-digit
+/// numbers, for [`Verdict::Possible`].
+const POSSIBLE_THRESHOLD: f64 = 0.5;
+
+/// How likely a column is to hold TINs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// At least 90% of the non-empty values are valid TINs.
+    Tin,
+    /// At least half of the non-empty values are valid TINs, or most are
+    /// nine-digit numbers that fail validation.
+    Possible,
+    /// The column does not look like a TIN column.
+    NotTin,
+    /// The column has no non-empty values.
+    Empty,
+}
+
+/// Counts gathered for one column.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColumnProfile {
+    index: usize,
+    name: Option<String>,
+    values: usize,
+    empty: usize,
+    ssn: usize,
+    itin: usize,
+    atin: usize,
+    ein: usize,
+    dashed: usize,
+    undashed: usize,
+    errors: BTreeMap<ErrorKind, usize>,
+}
+
+impl ColumnProfile {
+    fn new(index: usize, name: Option<String>) -> Self {
+        Self {
+            index,
+            name,
+            ..Self::default()
+        }
+    }
+
+    fn record(&mut self, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.empty += 1;
+            return;
+        }
+        self.values += 1;
+        match value.parse::<Tin>() {
+            Ok(tin) => {
+                match tin.kind() {
+                    TinKind::Ssn => self.ssn += 1,
+                    TinKind::Itin => self.itin += 1,
+                    TinKind::Atin => self.atin += 1,
+                    TinKind::Ein => self.ein += 1,
+                }
+                if Layout::of(value) == Some(Layout::Undashed) {
+                    self.undashed += 1;
+                } else {
+                    self.dashed += 1;
+                }
+            }
+            Err(err) => *self.errors.entry(err.kind()).or_default() += 1,
+        }
+    }
+
+    /// Returns the zero-based position of the column.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the column's header, if the file has headers.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of non-empty values.
+    pub fn values(&self) -> usize {
+        self.values
+    }
+
+    /// Returns the number of empty or all-whitespace values.
+    pub fn empty(&self) -> usize {
+        self.empty
+    }
+
+    /// Returns the number of values that parse as a [`Tin`] of the given kind.
+    pub fn count(&self, kind: TinKind) -> usize {
+        match kind {
+            TinKind::Ssn => self.ssn,
+            TinKind::Itin => self.itin,
+            TinKind::Atin => self.atin,
+            TinKind::Ein => self.ein,
+        }
+    }
+
+    /// Returns the number of values that parse as any [`Tin`].
+    pub fn valid(&self) -> usize {
+        self.dashed + self.undashed
+    }
+
+    /// Returns the number of valid values written with separators.
+    pub fn dashed(&self) -> usize {
+        self.dashed
+    }
+
+    /// Returns the number of valid values written as nine bare digits.
+    pub fn undashed(&self) -> usize {
+        self.undashed
+    }
+
+    /// Returns the number of values that failed with each kind of error.
+    pub fn errors(&self) -> &BTreeMap<ErrorKind, usize> {
+        &self.errors
+    }
+
+    /// Returns the share of non-empty values that parse as the given kind.
+    pub fn fraction(&self, kind: TinKind) -> f64 {
+        self.share(self.count(kind))
+    }
+
+    /// Returns the share of non-empty values that parse as any [`Tin`].
+    pub fn valid_fraction(&self) -> f64 {
+        self.share(self.valid())
+    }
+
+    /// Returns the most common valid kind, or `None` if no value is valid.
+    /// Ties go to the kind listed first in [`TinKind`].
+    pub fn dominant_kind(&self) -> Option<TinKind> {
+        // `max_by_key` keeps the last maximum, so reversing breaks ties in
+        // favor of the SSN family.
+        [TinKind::Ssn, TinKind::Itin, TinKind::Atin, TinKind::Ein]
+            .into_iter()
+            .rev()
+            .filter(|&kind| self.count(kind) > 0)
+            .max_by_key(|&kind| self.count(kind))
+    }
+
+    /// Returns whether the column looks like a TIN column.
+    pub fn verdict(&self) -> Verdict {
+        if self.values == 0 {
+            return Verdict::Empty;
+        }
+        let shaped = self.values - self.error_count(ErrorKind::InvalidFormat);
+        if self.valid_fraction() >= TIN_THRESHOLD {
+            Verdict::Tin
+        } else if self.valid_fraction() >= POSSIBLE_THRESHOLD || self.share(shaped) >= TIN_THRESHOLD
+        {
+            Verdict::Possible
+        } else {
+            Verdict::NotTin
+        }
+    }
+
+    fn error_count(&self, kind: ErrorKind) -> usize {
+        self.errors.get(&kind).copied().unwrap_or(0)
+    }
+
+    fn share(&self, count: usize) -> f64 {
+        if self.values == 0 {
+            0.0
+        } else {
+            count as f64 / self.values as f64
+        }
+    }
+}
+
+/// Settings for profiling a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Profiler {
+    has_headers: bool,
+    delimiter: u8,
+    max_rows: Option<usize>,
+}
+
+impl Profiler {
+    /// Returns a profiler for comma-separated files with a header row.
+    pub fn new() -> Self {
+        Self {
+            has_headers: true,
+            delimiter: b',',
+            max_rows: None,
+        }
+    }
+
+    /// Sets whether the first row holds column names.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Sets the field delimiter.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Stops after `max_rows` data rows, profiling a sample of a large file.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Profiles every column of the CSV read from `reader`.
+    ///
+    /// Rows may have different lengths; a column that only some rows reach
+    /// is profiled over those rows.
+    pub fn profile<R: io::Read>(&self, reader: R) -> Result<Vec<ColumnProfile>, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(self.has_headers)
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_reader(reader);
+
+        let headers: Vec<String> = if self.has_headers {
+            reader.headers()?.iter().map(str::to_owned).collect()
+        } else {
+            Vec::new()
+        };
+        let mut columns: Vec<ColumnProfile> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ColumnProfile::new(i, Some(name.clone())))
+            .collect();
+
+        let mut record = csv::StringRecord::new();
+        let mut rows = 0;
+        while self.max_rows.is_none_or(|max| rows < max) && reader.read_record(&mut record)? {
+            rows += 1;
+            for (i, value) in record.iter().enumerate() {
+                if i == columns.len() {
+                    columns.push(ColumnProfile::new(i, None));
+                }
+                columns[i].record(value);
+            }
+        }
+        Ok(columns)
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYEES: &str = "\
+name,payee_tin,zip,notes
+Ada,123-45-6789,02139,
+Bob,900701234,10001,call back
+Cy,12-3456789,94105,
+Di,000-12-3456,60601,
+Ed,123456789,73301,
+";
+
+    fn profile(data: &str) -> Vec<ColumnProfile> {
+        Profiler::new().profile(data.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn finds_the_tin_column() {
+        let columns = profile(PAYEES);
+        let verdicts: Vec<_> = columns.iter().map(ColumnProfile::verdict).collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::NotTin,
+                Verdict::Possible,
+                Verdict::NotTin,
+                Verdict::NotTin
+            ]
+        );
+        assert_eq!(columns[1].name(), Some("payee_tin"));
+    }
+
+    #[test]
+    fn counts_kinds_and_layouts() {
+        let tin = &profile(PAYEES)[1];
+        assert_eq!(tin.values(), 5);
+        assert_eq!(tin.count(TinKind::Ssn), 2);
+        assert_eq!(tin.count(TinKind::Itin), 1);
+        assert_eq!(tin.count(TinKind::Ein), 1);
+        assert_eq!(tin.valid_fraction(), 0.8);
+        assert_eq!(tin.fraction(TinKind::Ssn), 0.4);
+        assert_eq!((tin.dashed(), tin.undashed()), (2, 2));
+        assert_eq!(tin.dominant_kind(), Some(TinKind::Ssn));
+    }
+
+    #[test]
+    fn reports_error_distribution() {
+        let columns = profile(PAYEES);
+        assert_eq!(
+            columns[1].errors().iter().collect::<Vec<_>>(),
+            [(&ErrorKind::InvalidArea, &1)]
+        );
+        assert_eq!(columns[0].errors().get(&ErrorKind::InvalidFormat), Some(&5));
+    }
+
+    #[test]
+    fn empty_values_are_not_counted() {
+        let notes = &profile(PAYEES)[3];
+        assert_eq!((notes.values(), notes.empty()), (1, 4));
+        assert_eq!(profile("a\n\n \n")[0].verdict(), Verdict::Empty);
+    }
+
+    #[test]
+    fn invalid_nine_digit_column_is_possible() {
+        let data = "id\n000-00-0000\n666-12-3456\n000123456\n";
+        assert_eq!(profile(data)[0].verdict(), Verdict::Possible);
+    }
+
+    #[test]
+    fn headerless_and_ragged_rows() {
+        let columns = Profiler::new()
+            .has_headers(false)
+            .delimiter(b';')
+            .profile("123-45-6789\nx;900-70-1234\n".as_bytes())
+            .unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].name(), None);
+        assert_eq!(columns[1].index(), 1);
+        assert_eq!(columns[1].values(), 1);
+        assert_eq!(columns[1].verdict(), Verdict::Tin);
+    }
+
+    #[test]
+    fn max_rows_samples() {
+        let columns = Profiler::new()
+            .max_rows(2)
+            .profile(PAYEES.as_bytes())
+            .unwrap();
+        assert_eq!(columns[1].values(), 2);
+    }
+}
@@ -0,0 +1,216 @@
+//! U.S. Preparer Tax Identification Number (PTIN) validation.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{FormatError, ParseError};
+
+/// A validated U.S. Preparer Tax Identification Number.
+///
+/// # Validation
+///
+/// Per [IRS rules](https://www.irs.gov/tax-professionals/ptin-requirements-for-tax-return-preparers):
+/// - Must be the letter `P` followed by 8 digits
+/// - Number (the 8 digits) must be 00000001–99999999
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
+pub struct Ptin {
+    number: u32,
+}
+
+impl Ptin {
+    /// Creates a new PTIN from the number following the `P`.
+    pub fn new(number: u32) -> Result<Self, ParseError> {
+        Self::validate(number)?;
+        Ok(Self { number })
+    }
+
+    fn validate(number: u32) -> Result<(), ParseError> {
+        if number == 0 || number > 99_999_999 {
+            return Err(ParseError::InvalidPtinNumber(number));
+        }
+        Ok(())
+    }
+
+    /// Returns the number (the 8 digits following the `P`).
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+}
+
+impl FromStr for Ptin {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |position| Err(ParseError::InvalidFormat(FormatError::at(s, position)));
+        let Some(prefix) = s.chars().next() else {
+            return invalid(0);
+        };
+        let start = prefix.len_utf8();
+        let digits = &s.as_bytes()[start..];
+        // Everything before the first non-digit is ASCII, so its offset
+        // falls on a character boundary.
+        match digits.iter().take(8).position(|b| !b.is_ascii_digit()) {
+            Some(i) => return invalid(start + i),
+            None if digits.len() != 8 => return invalid(start + digits.len().min(8)),
+            None => {}
+        }
+        if prefix != 'P' {
+            return Err(ParseError::MissingPtinPrefix);
+        }
+        let number = digits
+            .iter()
+            .fold(0, |acc, &b| acc * 10 + u32::from(b - b'0'));
+        Self::new(number)
+    }
+}
+
+impl fmt::Display for Ptin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "P{:08}", self.number)
+    }
+}
+
+impl fmt::Debug for Ptin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ptin(PXXXX{:04})", self.number % 10_000)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Ptin {
+    /// Overwrites the number with zero.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.number.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Ptin {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.number.ct_eq(&other.number)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Ptin {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Ptin {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Ptin {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_u32(self.number);
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Ptin {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Ptin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharClass;
+
+    #[test]
+    fn valid_ptin() {
+        let ptin: Ptin = "P01234567".parse().unwrap();
+        assert_eq!(ptin.number(), 1_234_567);
+        assert_eq!(ptin.to_string(), "P01234567");
+    }
+
+    #[test]
+    fn valid_boundaries() {
+        assert_eq!(Ptin::new(1).unwrap().to_string(), "P00000001");
+        assert_eq!(Ptin::new(99_999_999).unwrap().to_string(), "P99999999");
+    }
+
+    #[test]
+    fn invalid_number() {
+        assert!(matches!(
+            "P00000000".parse::<Ptin>(),
+            Err(ParseError::InvalidPtinNumber(0))
+        ));
+        assert!(matches!(
+            Ptin::new(100_000_000),
+            Err(ParseError::InvalidPtinNumber(100_000_000))
+        ));
+    }
+
+    #[test]
+    fn missing_prefix() {
+        assert!(matches!(
+            "p01234567".parse::<Ptin>(),
+            Err(ParseError::MissingPtinPrefix)
+        ));
+        assert!(matches!(
+            "X01234567".parse::<Ptin>(),
+            Err(ParseError::MissingPtinPrefix)
+        ));
+    }
+
+    #[test]
+    fn invalid_format() {
+        for input in ["", "P", "P1234567", "P123456789", "P1234567a", "01234567"] {
+            assert!(
+                matches!(input.parse::<Ptin>(), Err(ParseError::InvalidFormat(_))),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_errors_point_at_the_offending_character() {
+        for (input, position, class) in [
+            ("Pé", 1, CharClass::Other),
+            ("P1234567é", 8, CharClass::Other),
+            ("é1234567a", 9, CharClass::Letter),
+            ("P1234567", 8, CharClass::End),
+            ("P123456789", 9, CharClass::Digit),
+            ("", 0, CharClass::End),
+        ] {
+            let Err(ParseError::InvalidFormat(err)) = input.parse::<Ptin>() else {
+                panic!("{input}: expected a format error");
+            };
+            assert_eq!((err.position(), err.class()), (position, class), "{input}");
+        }
+    }
+
+    #[test]
+    fn debug_masks_sensitive_data() {
+        let ptin: Ptin = "P01234567".parse().unwrap();
+        assert_eq!(format!("{ptin:?}"), "Ptin(PXXXX4567)");
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut ptin: Ptin = "P01234567".parse().unwrap();
+        let same: Ptin = "P01234567".parse().unwrap();
+        let mut other = ptin.clone();
+        other.number += 1;
+        assert_eq!(ptin, same);
+        assert_ne!(ptin, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&ptin), state.hash_one(&same));
+
+        ptin.wipe();
+        assert_eq!(ptin.number(), 0);
+    }
+}
@@ -0,0 +1,538 @@
+//! Streaming redaction of SSNs, ITINs, and ATINs.
+//!
+//! [`RedactingWriter`] and [`RedactingReader`] wrap an [`io::Write`] or
+//! [`io::Read`] and replace every number found by [`scan`] with
+//! a [`Mask`]. They hold back only the last few bytes of input, so a number
+//! split across two writes or reads is still found, and memory stays bounded
+//! regardless of stream length.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::Write;
+//! use tin::redact::{Mask, Redactor};
+//!
+//! let mut writer = Redactor::new().mask(Mask::LastFour).writer(Vec::new());
+//! writer.write_all(b"user ssn=123-45")?;
+//! writer.write_all(b"-6789 logged in\n")?;
+//! let out = writer.finish()?;
+//! assert_eq!(out, b"user ssn=XXX-XX-6789 logged in\n");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::scan;
+
+#[cfg(feature = "json")]
+pub mod json;
+
+/// Longest match (`XXX-XX-XXXX`) plus the two bytes the boundary check reads
+/// after it. Input within this distance of the end may still change meaning.
+const HOLD: usize = 13;
+
+/// Bytes of already-emitted input kept for the boundary check before a match.
+const LOOKBEHIND: usize = 2;
+
+/// Largest slice of input processed at once.
+const CHUNK: usize = 8 * 1024;
+
+/// What a redacted number is replaced with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Mask {
+    /// `XXX-XX-1234`, keeping the last 4 digits as `Debug` does.
+    LastFour,
+    /// `XXX-XX-XXXX`.
+    Full,
+    /// A fixed replacement string.
+    Text(String),
+}
+
+/// Redaction settings, and a factory for redacting readers and writers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Redactor {
+    mask: Mask,
+    validated_only: bool,
+}
+
+impl Redactor {
+    /// Returns a redactor that masks every TIN-shaped number as `XXX-XX-1234`.
+    pub fn new() -> Self {
+        Self {
+            mask: Mask::LastFour,
+            validated_only: false,
+        }
+    }
+
+    /// Sets the replacement for each redacted number.
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Sets whether only numbers that validate as an SSN, ITIN, or ATIN are
+    /// redacted, leaving numbers that merely have the shape untouched.
+    pub fn validated_only(mut self, validated_only: bool) -> Self {
+        self.validated_only = validated_only;
+        self
+    }
+
+    /// Wraps a writer so everything written through it is redacted.
+    pub fn writer<W: Write>(self, inner: W) -> RedactingWriter<W> {
+        RedactingWriter {
+            inner: Some(inner),
+            core: Core::new(self),
+            out: Vec::new(),
+        }
+    }
+
+    /// Wraps a reader so everything read from it is redacted.
+    pub fn reader<R: Read>(self, inner: R) -> RedactingReader<R> {
+        RedactingReader {
+            inner,
+            core: Core::new(self),
+            out: Vec::new(),
+            out_pos: 0,
+            done: false,
+        }
+    }
+
+    /// Redacts a complete string.
+    pub fn redact(&self, text: &str) -> String {
+        let mut core = Core::new(self.clone());
+        let mut out = Vec::with_capacity(text.len());
+        core.push(text.as_bytes(), &mut out);
+        core.finish(&mut out);
+        String::from_utf8(out).expect("only ASCII digit runs are replaced, with UTF-8 masks")
+    }
+
+    fn write_mask(&self, matched: &[u8], out: &mut Vec<u8>) {
+        match &self.mask {
+            Mask::LastFour => {
+                out.extend_from_slice(b"XXX-XX-");
+                out.extend_from_slice(&matched[matched.len() - 4..]);
+            }
+            Mask::Full => out.extend_from_slice(b"XXX-XX-XXXX"),
+            Mask::Text(text) => out.extend_from_slice(text.as_bytes()),
+        }
+    }
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Incremental redaction state shared by the reader and writer.
+#[derive(Debug)]
+struct Core {
+    redactor: Redactor,
+    /// Unemitted input, preceded by `context` bytes of emitted input.
+    buf: Vec<u8>,
+    context: usize,
+}
+
+impl Core {
+    fn new(redactor: Redactor) -> Self {
+        Self {
+            redactor,
+            buf: Vec::new(),
+            context: 0,
+        }
+    }
+
+    /// Consumes input, appending every byte that can no longer change to `out`.
+    fn push(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        for chunk in input.chunks(CHUNK) {
+            self.buf.extend_from_slice(chunk);
+            self.process(out, false);
+        }
+    }
+
+    /// Appends all remaining output, treating the input as ended.
+    fn finish(&mut self, out: &mut Vec<u8>) {
+        self.process(out, true);
+    }
+
+    /// Appends every held-back byte that can no longer be part of a match,
+    /// keeping only a trailing run of digits and separators.
+    fn flush(&mut self, out: &mut Vec<u8>) {
+        let run = self.buf[self.context..]
+            .iter()
+            .rev()
+            .take_while(|&&b| b.is_ascii_digit() || matches!(b, b'-' | b' ' | b'.' | b'/'))
+            .count();
+        let safe = self.buf.len() - run;
+        self.process_to(out, safe.max(self.safe()));
+    }
+
+    fn process(&mut self, out: &mut Vec<u8>, eof: bool) {
+        let safe = if eof { self.buf.len() } else { self.safe() };
+        self.process_to(out, safe);
+    }
+
+    /// Returns the offset before which no new input can change a match.
+    fn safe(&self) -> usize {
+        self.buf.len().saturating_sub(HOLD).max(self.context)
+    }
+
+    /// Emits input before `safe`, redacting the matches that start there.
+    fn process_to(&mut self, out: &mut Vec<u8>, safe: usize) {
+        let mut pos = self.context;
+        let mut search = pos;
+        while let Some(m) = scan::find_at(&self.buf, search) {
+            if m.start() >= safe {
+                break;
+            }
+            search = m.end();
+            if self.redactor.validated_only && !m.is_valid() {
+                continue;
+            }
+            out.extend_from_slice(&self.buf[pos..m.start()]);
+            self.redactor.write_mask(&self.buf[m.range()], out);
+            pos = m.end();
+        }
+        let emit_to = safe.max(pos);
+        out.extend_from_slice(&self.buf[pos..emit_to]);
+
+        let keep_from = emit_to.saturating_sub(LOOKBEHIND);
+        self.buf.drain(..keep_from);
+        self.context = emit_to - keep_from;
+    }
+}
+
+/// A writer that redacts TINs before passing bytes to the inner writer.
+///
+/// The last few bytes written are held back until more input shows whether
+/// they belong to a TIN. [`flush`](Write::flush) writes those that cannot,
+/// but a trailing run of digits and separators stays held back until more
+/// input arrives or [`finish`](Self::finish) is called.
+///
+/// **Call `finish` when done.** Dropping the writer also writes the
+/// held-back bytes, but ignores any error in doing so.
+#[derive(Debug)]
+pub struct RedactingWriter<W: Write> {
+    /// `None` only once `finish` has returned it.
+    inner: Option<W>,
+    core: Core,
+    /// Redacted output not yet accepted by `inner`.
+    out: Vec<u8>,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    /// Writes the held-back bytes, flushes, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.finish_stream()?;
+        Ok(self
+            .inner
+            .take()
+            .expect("inner writer is present until finish"))
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner
+            .as_ref()
+            .expect("inner writer is present until finish")
+    }
+
+    fn inner(&mut self) -> &mut W {
+        self.inner
+            .as_mut()
+            .expect("inner writer is present until finish")
+    }
+
+    fn finish_stream(&mut self) -> io::Result<()> {
+        self.core.finish(&mut self.out);
+        self.write_out()?;
+        self.inner().flush()
+    }
+
+    /// Writes `out` to the inner writer, removing whatever it accepted even
+    /// if it then fails.
+    fn write_out(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.out.len() {
+                break Ok(());
+            }
+            let out = &self.out[written..];
+            match self.inner.as_mut().map(|inner| inner.write(out)) {
+                Some(Ok(0)) => break Err(io::ErrorKind::WriteZero.into()),
+                Some(Ok(n)) => written += n,
+                Some(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+                Some(Err(e)) => break Err(e),
+                None => break Ok(()),
+            }
+        };
+        self.out.drain(..written);
+        result
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    /// Redacts `buf` and writes the result.
+    ///
+    /// If the inner writer fails before accepting any of the result, `buf`
+    /// is not consumed and the call can be retried. If it fails partway,
+    /// the rest is kept and written first by the next call.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_out()?;
+        let saved = (self.core.buf.clone(), self.core.context);
+        self.core.push(buf, &mut self.out);
+        let pending = self.out.len();
+        match self.write_out() {
+            Err(e) if self.out.len() == pending => {
+                (self.core.buf, self.core.context) = saved;
+                self.out.clear();
+                Err(e)
+            }
+            _ => Ok(buf.len()),
+        }
+    }
+
+    /// Writes every held-back byte that can no longer be part of a TIN, then
+    /// flushes the inner writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.core.flush(&mut self.out);
+        self.write_out()?;
+        self.inner().flush()
+    }
+}
+
+/// Finishes the stream on a best-effort basis, ignoring errors.
+impl<W: Write> Drop for RedactingWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.finish_stream();
+        }
+    }
+}
+
+/// A reader that redacts TINs in the bytes read from the inner reader.
+#[derive(Debug)]
+pub struct RedactingReader<R: Read> {
+    inner: R,
+    core: Core,
+    out: Vec<u8>,
+    out_pos: usize,
+    done: bool,
+}
+
+impl<R: Read> RedactingReader<R> {
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for RedactingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.out_pos < self.out.len() {
+                let n = buf.len().min(self.out.len() - self.out_pos);
+                buf[..n].copy_from_slice(&self.out[self.out_pos..self.out_pos + n]);
+                self.out_pos += n;
+                return Ok(n);
+            }
+            if self.done {
+                return Ok(0);
+            }
+            self.out.clear();
+            self.out_pos = 0;
+
+            let mut chunk = [0u8; CHUNK];
+            let n = match self.inner.read(&mut chunk) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if n == 0 {
+                self.core.finish(&mut self.out);
+                self.done = true;
+            } else {
+                self.core.push(&chunk[..n], &mut self.out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ssn 123-45-6789, itin 900701234, phone 555-123-4567, \
+                         bad 000-12-3456, long 12345678901, end 900 93 1234";
+    const REDACTED: &str = "ssn XXX-XX-6789, itin XXX-XX-1234, phone 555-123-4567, \
+                            bad XXX-XX-3456, long 12345678901, end XXX-XX-1234";
+
+    fn write_in_chunks(input: &[u8], size: usize, redactor: Redactor) -> Vec<u8> {
+        let mut writer = redactor.writer(Vec::new());
+        for chunk in input.chunks(size) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    /// A reader that returns at most `size` bytes per call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        size: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.size.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn redacts_string() {
+        assert_eq!(Redactor::new().redact(INPUT), REDACTED);
+    }
+
+    #[test]
+    fn writer_handles_every_split() {
+        for size in 1..=INPUT.len() {
+            let out = write_in_chunks(INPUT.as_bytes(), size, Redactor::new());
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                REDACTED,
+                "chunk size {size}"
+            );
+        }
+    }
+
+    #[test]
+    fn reader_handles_every_split() {
+        for size in 1..=INPUT.len() {
+            let mut reader = Redactor::new().reader(Trickle {
+                data: INPUT.as_bytes(),
+                size,
+            });
+            let mut out = String::new();
+            reader.read_to_string(&mut out).unwrap();
+            assert_eq!(out, REDACTED, "chunk size {size}");
+        }
+    }
+
+    #[test]
+    fn boundary_context_survives_splits() {
+        // The match must not be reported when the preceding digits arrive
+        // in an earlier write.
+        let input = b"x 1-123-45-6789 y";
+        for size in 1..=input.len() {
+            assert_eq!(write_in_chunks(input, size, Redactor::new()), input);
+        }
+    }
+
+    #[test]
+    fn masks() {
+        let text = "a 123-45-6789 b";
+        assert_eq!(
+            Redactor::new().mask(Mask::Full).redact(text),
+            "a XXX-XX-XXXX b"
+        );
+        assert_eq!(
+            Redactor::new()
+                .mask(Mask::Text("[REDACTED]".into()))
+                .redact(text),
+            "a [REDACTED] b"
+        );
+    }
+
+    #[test]
+    fn validated_only() {
+        let redactor = Redactor::new().validated_only(true);
+        assert_eq!(
+            redactor.redact("000-12-3456 123-45-6789"),
+            "000-12-3456 XXX-XX-6789"
+        );
+    }
+
+    #[test]
+    fn memory_stays_bounded() {
+        let line = "id 123-45-6789 ok\n".repeat(10_000);
+        let mut writer = Redactor::new().writer(io::sink());
+        writer.write_all(line.as_bytes()).unwrap();
+        assert!(writer.core.buf.len() <= HOLD + LOOKBEHIND);
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn flush_writes_bytes_that_cannot_start_a_tin() {
+        let mut writer = Redactor::new().writer(Vec::new());
+        writer.write_all(b"hello world, goodbye\n").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref(), b"hello world, goodbye\n");
+
+        writer.write_all(b"id 123-45").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref(), b"hello world, goodbye\nid");
+        // A trailing `.` could still join the number to a following digit.
+        writer.write_all(b"-6789.").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref(), b"hello world, goodbye\nid");
+        writer.write_all(b" done").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(
+            writer.get_ref(),
+            b"hello world, goodbye\nid XXX-XX-6789. done"
+        );
+    }
+
+    #[test]
+    fn drop_writes_the_held_back_bytes() {
+        let mut out = Vec::new();
+        let mut writer = Redactor::new().writer(&mut out);
+        writer.write_all(b"ssn 123-45-6789").unwrap();
+        drop(writer);
+        assert_eq!(out, b"ssn XXX-XX-6789");
+    }
+
+    /// A writer that fails its first `failures` calls.
+    struct Flaky {
+        failures: usize,
+        data: Vec<u8>,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(io::ErrorKind::Other.into());
+            }
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failed_writes_do_not_consume_input() {
+        let input = b"ssn 123-45-6789 and more text after it\n";
+        let mut writer = Redactor::new().writer(Flaky {
+            failures: 1,
+            data: Vec::new(),
+        });
+        assert!(writer.write(input).is_err());
+        writer.write_all(input).unwrap();
+        let out = writer.finish().unwrap().data;
+        assert_eq!(out, b"ssn XXX-XX-6789 and more text after it\n");
+    }
+
+    #[test]
+    fn preserves_utf8() {
+        assert_eq!(
+            Redactor::new().redact("née 123-45-6789 — ok"),
+            "née XXX-XX-6789 — ok"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Structure-aware redaction of JSON documents.
+//!
+//! [`JsonRedactor`] walks a [`serde_json::Value`] and masks TINs in place.
+//! Values under a sensitive field name (`ssn`, `taxId`, `tin`, …) are masked
+//! whatever their content, and every other string is scanned like free text.
+//! Each value keeps its type, so the document still fits its schema, and the
+//! [JSON pointers](https://www.rfc-editor.org/rfc/rfc6901) of the changed
+//! values are returned for audit logs.
+//!
+//! # Example
+//!
+//! ```
+//! use serde_json::json;
+//! use tin::redact::json::JsonRedactor;
+//!
+//! let mut body = json!({
+//!     "customer": { "taxId": "123-45-6789", "note": "spouse 900-70-1234" },
+//!     "applicant_ssn": 123456789,
+//!     "orderId": 42,
+//! });
+//! let changed = JsonRedactor::new().redact(&mut body);
+//! assert_eq!(
+//!     body,
+//!     json!({
+//!         "customer": { "taxId": "XXX-XX-6789", "note": "spouse XXX-XX-1234" },
+//!         "applicant_ssn": 0,
+//!         "orderId": 42,
+//!     })
+//! );
+//! assert_eq!(changed, ["/applicant_ssn", "/customer/note", "/customer/taxId"]);
+//! ```
+
+use serde_json::Value;
+
+use super::{Mask, Redactor};
+use crate::Tin;
+
+/// Field names whose values are always masked, in normalized form.
+const DEFAULT_FIELDS: &[&str] = &[
+    "ssn",
+    "socialsecurity",
+    "socialsecuritynumber",
+    "socialsecurityno",
+    "tin",
+    "taxid",
+    "taxidentifier",
+    "taxidentificationnumber",
+    "taxpayerid",
+    "taxpayeridentificationnumber",
+    "itin",
+    "atin",
+    "ein",
+    "fein",
+    "employeridentificationnumber",
+];
+
+/// Redaction settings for JSON documents.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonRedactor {
+    redactor: Redactor,
+    fields: Vec<String>,
+    detect_content: bool,
+}
+
+impl JsonRedactor {
+    /// Returns a JSON redactor with the default field names and content
+    /// detection enabled, masking as `XXX-XX-1234`.
+    pub fn new() -> Self {
+        Self {
+            redactor: Redactor::new(),
+            fields: DEFAULT_FIELDS.iter().map(|&f| f.to_owned()).collect(),
+            detect_content: true,
+        }
+    }
+
+    /// Sets the masking used for strings. Only the redactor's [`Mask`] is
+    /// used for sensitive fields; its other settings apply to content
+    /// detection.
+    pub fn redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Adds a sensitive field name.
+    ///
+    /// Field names are compared without case or punctuation, so `taxId`,
+    /// `tax_id`, and `Tax-ID` are the same name. A field also matches when
+    /// its trailing words do, so `customerSsn` and `spouse_tax_id` match
+    /// `ssn` and `taxId`, but `martin` does not match `tin`.
+    pub fn field(mut self, name: &str) -> Self {
+        self.fields.push(normalize(name));
+        self
+    }
+
+    /// Removes every sensitive field name, including the defaults.
+    pub fn clear_fields(mut self) -> Self {
+        self.fields.clear();
+        self
+    }
+
+    /// Sets whether strings outside sensitive fields are scanned for TINs.
+    pub fn detect_content(mut self, detect_content: bool) -> Self {
+        self.detect_content = detect_content;
+        self
+    }
+
+    /// Masks TINs in `value` in place, returning the JSON pointer of every
+    /// value that changed, in the order visited.
+    ///
+    /// Under a sensitive field, strings are masked, numbers become `0`, and
+    /// arrays and objects are masked throughout. Booleans and nulls are left
+    /// alone. Numbers outside sensitive fields are never changed.
+    pub fn redact(&self, value: &mut Value) -> Vec<String> {
+        let mut changed = Vec::new();
+        let mut path = String::new();
+        self.walk(value, false, &mut path, &mut changed);
+        changed
+    }
+
+    fn walk(
+        &self,
+        value: &mut Value,
+        sensitive: bool,
+        path: &mut String,
+        changed: &mut Vec<String>,
+    ) {
+        let replacement = match value {
+            Value::Null | Value::Bool(_) => None,
+            Value::Number(n) => (sensitive && !is_zero(n)).then(|| Value::from(0)),
+            Value::String(s) => {
+                let masked = if sensitive {
+                    self.mask_field(s)
+                } else if self.detect_content {
+                    self.redactor.redact(s)
+                } else {
+                    return;
+                };
+                (masked != *s).then_some(Value::String(masked))
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(&i.to_string());
+                    self.walk(item, sensitive, path, changed);
+                    path.truncate(len);
+                }
+                None
+            }
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    let len = path.len();
+                    path.push('/');
+                    push_escaped(path, key);
+                    let sensitive = sensitive || self.is_sensitive(key);
+                    self.walk(item, sensitive, path, changed);
+                    path.truncate(len);
+                }
+                None
+            }
+        };
+        if let Some(replacement) = replacement {
+            *value = replacement;
+            changed.push(path.clone());
+        }
+    }
+
+    /// Masks the whole of a string found under a sensitive field.
+    fn mask_field(&self, s: &str) -> String {
+        match &self.redactor.mask {
+            Mask::Text(text) => text.clone(),
+            Mask::LastFour => match s.trim().parse::<Tin>() {
+                Ok(tin) => tin.masked().to_string(),
+                Err(_) => mask_digits(s),
+            },
+            Mask::Full => mask_digits(s),
+        }
+    }
+
+    fn is_sensitive(&self, key: &str) -> bool {
+        let words = words(key);
+        (0..words.len()).any(|i| {
+            let suffix = words[i..].concat();
+            self.fields.contains(&suffix)
+        })
+    }
+}
+
+impl Default for JsonRedactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replaces every ASCII digit with `X`, keeping the punctuation.
+fn mask_digits(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_digit() { 'X' } else { c })
+        .collect()
+}
+
+fn is_zero(n: &serde_json::Number) -> bool {
+    n.as_u64() == Some(0) || n.as_i64() == Some(0) || n.as_f64() == Some(0.0)
+}
+
+/// Appends `key` to a JSON pointer, escaping `~` and `/`.
+fn push_escaped(path: &mut String, key: &str) {
+    for c in key.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+}
+
+/// Lowercases a field name and drops everything but letters and digits.
+fn normalize(name: &str) -> String {
+    words(name).concat()
+}
+
+/// Splits a field name into lowercase words at punctuation and at
+/// camelCase boundaries: `customerTaxIDNumber` becomes
+/// `["customer", "tax", "id", "number"]`.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(core::mem::take(&mut word));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let starts_word = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            });
+        if starts_word && !word.is_empty() {
+            words.push(core::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn masks_sensitive_fields_by_name() {
+        let mut value = json!({
+            "ssn": "123-45-6789",
+            "TaxID": "12-3456789",
+            "customer_tin": "not a tin 42",
+            "itin": 900701234,
+            "ein": ["12-3456789", 0, null, true],
+        });
+        let changed = JsonRedactor::new().redact(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "ssn": "XXX-XX-6789",
+                "TaxID": "XX-XXX6789",
+                "customer_tin": "not a tin XX",
+                "itin": 0,
+                "ein": ["XX-XXX6789", 0, null, true],
+            })
+        );
+        assert_eq!(
+            changed,
+            ["/TaxID", "/customer_tin", "/ein/0", "/itin", "/ssn"]
+        );
+    }
+
+    #[test]
+    fn nested_values_under_sensitive_fields_are_masked() {
+        let mut value = json!({ "ssn": { "primary": "123456789", "last4": 6789 } });
+        JsonRedactor::new().redact(&mut value);
+        assert_eq!(
+            value,
+            json!({ "ssn": { "primary": "XXX-XX-6789", "last4": 0 } })
+        );
+    }
+
+    #[test]
+    fn detects_content_in_other_strings() {
+        let mut value = json!({ "notes": ["call re 123-45-6789", "order 1234567890"] });
+        let changed = JsonRedactor::new().redact(&mut value);
+        assert_eq!(
+            value,
+            json!({ "notes": ["call re XXX-XX-6789", "order 1234567890"] })
+        );
+        assert_eq!(changed, ["/notes/0"]);
+
+        let mut value = json!({ "notes": "123-45-6789" });
+        let changed = JsonRedactor::new().detect_content(false).redact(&mut value);
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn numbers_outside_sensitive_fields_are_kept() {
+        let mut value = json!({ "orderId": 123456789 });
+        assert!(JsonRedactor::new().redact(&mut value).is_empty());
+        assert_eq!(value, json!({ "orderId": 123456789 }));
+    }
+
+    #[test]
+    fn field_names_match_on_trailing_words() {
+        let redactor = JsonRedactor::new();
+        for key in [
+            "ssn",
+            "SSN",
+            "customerSsn",
+            "spouse_tax_id",
+            "Tax-ID",
+            "taxpayerIdentificationNumber",
+            "employer.ein",
+        ] {
+            assert!(redactor.is_sensitive(key), "{key}");
+        }
+        for key in ["martin", "destination", "ssnVerified", "protein"] {
+            assert!(!redactor.is_sensitive(key), "{key}");
+        }
+    }
+
+    #[test]
+    fn custom_fields() {
+        let redactor = JsonRedactor::new().clear_fields().field("national_id");
+        let mut value = json!({ "nationalId": "AB12", "ssn": "x" });
+        assert_eq!(redactor.redact(&mut value), ["/nationalId"]);
+        assert_eq!(value, json!({ "nationalId": "ABXX", "ssn": "x" }));
+    }
+
+    #[test]
+    fn masks_follow_the_redactor() {
+        let mut value = json!({ "ssn": "123-45-6789", "note": "123-45-6789" });
+        JsonRedactor::new()
+            .redactor(Redactor::new().mask(Mask::Text("[REDACTED]".into())))
+            .redact(&mut value);
+        assert_eq!(value, json!({ "ssn": "[REDACTED]", "note": "[REDACTED]" }));
+
+        let mut value = json!({ "ssn": "123-45-6789" });
+        JsonRedactor::new()
+            .redactor(Redactor::new().mask(Mask::Full))
+            .redact(&mut value);
+        assert_eq!(value, json!({ "ssn": "XXX-XX-XXXX" }));
+    }
+
+    #[test]
+    fn pointers_are_escaped() {
+        let mut value = json!({ "a/b": { "c~d": "123-45-6789" } });
+        assert_eq!(JsonRedactor::new().redact(&mut value), ["/a~1b/c~0d"]);
+    }
+
+    #[test]
+    fn words_split_camel_case_and_acronyms() {
+        assert_eq!(
+            words("customerTaxIDNumber"),
+            ["customer", "tax", "id", "number"]
+        );
+        assert_eq!(words("SSN_last4"), ["ssn", "last4"]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Finding SSNs, ITINs, and ATINs in free text.
+//!
+//! The scanner looks for `XXX-XX-XXXX`, `XXX XX XXXX`, and `XXXXXXXXX` on
+//! word boundaries, so digits inside longer numbers — phone numbers, account
+//! numbers, `1-123-45-6789` — are not reported. Each match says whether it
+//! passed full validation or only has the right shape.
+//!
+//! # Example
+//!
+//! ```
+//! use tin::TinKind;
+//!
+//! let text = "Customer SSN 123-45-6789, ITIN 900701234, order 1234567890.";
+//! let matches: Vec<_> = tin::scan::find_iter(text).collect();
+//! assert_eq!(matches.len(), 2);
+//! assert_eq!(&text[matches[0].range()], "123-45-6789");
+//! assert_eq!(matches[1].kind(), TinKind::Itin);
+//! assert!(matches[1].is_valid());
+//! ```
+
+use core::ops::Range;
+
+use crate::confidence::{self, Confidence};
+use crate::{Tin, TinKind, components, parse};
+
+/// A TIN-shaped number found in a haystack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Match {
+    start: usize,
+    end: usize,
+    kind: TinKind,
+    tin: Option<Tin>,
+}
+
+impl Match {
+    /// Returns the byte offset of the first character of the match.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset just past the last character of the match.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the byte span of the match.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the detected kind.
+    ///
+    /// For a match that does not validate, this is the kind its area and
+    /// group point to: ATIN for area 900–999 with group 93, ITIN for other
+    /// 900–999 areas, and SSN otherwise.
+    pub fn kind(&self) -> TinKind {
+        self.kind
+    }
+
+    /// Returns `true` if the match is a valid SSN, ITIN, or ATIN.
+    pub fn is_valid(&self) -> bool {
+        self.tin.is_some()
+    }
+
+    /// Returns the validated TIN, or `None` if the match only has the shape.
+    pub fn tin(&self) -> Option<&Tin> {
+        self.tin.as_ref()
+    }
+
+    /// Scores how likely the match is to be a real TIN, using the text
+    /// around it. `haystack` must be the text the match was found in.
+    ///
+    /// See [`confidence`] for the signals used.
+    pub fn confidence<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Confidence {
+        confidence::score(haystack, self)
+    }
+}
+
+/// An iterator over the TIN-shaped numbers in a haystack.
+///
+/// Created by [`find_iter`].
+#[derive(Debug, Clone)]
+pub struct FindIter<'h> {
+    haystack: &'h [u8],
+    pos: usize,
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let m = find_at(self.haystack, self.pos)?;
+        self.pos = m.end;
+        Some(m)
+    }
+}
+
+/// Returns an iterator over the TIN-shaped numbers in `haystack`.
+pub fn find_iter<H: AsRef<[u8]> + ?Sized>(haystack: &H) -> FindIter<'_> {
+    FindIter {
+        haystack: haystack.as_ref(),
+        pos: 0,
+    }
+}
+
+/// Returns an iterator over the TIN-shaped numbers in `haystack` that start
+/// at or after `start`.
+///
+/// Bytes before `start` still count for word boundaries, so a stream can be
+/// scanned in pieces by keeping the end of the previous piece.
+///
+/// # Example
+///
+/// ```
+/// let text = "1-123-45-6789 and 234-56-7890";
+/// let matches: Vec<_> = tin::scan::find_iter_at(text, 2).collect();
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].start(), 18);
+/// ```
+pub fn find_iter_at<H: AsRef<[u8]> + ?Sized>(haystack: &H, start: usize) -> FindIter<'_> {
+    FindIter {
+        haystack: haystack.as_ref(),
+        pos: start,
+    }
+}
+
+/// Returns the first match starting at or after `pos`.
+///
+/// Bytes before `pos` are still consulted for the word boundary.
+pub(crate) fn find_at(haystack: &[u8], pos: usize) -> Option<Match> {
+    (pos..haystack.len()).find_map(|start| match_at(haystack, start))
+}
+
+/// Returns the match starting exactly at `start`, if any.
+fn match_at(haystack: &[u8], start: usize) -> Option<Match> {
+    if !haystack[start].is_ascii_digit() {
+        return None;
+    }
+    let (end, separator, digits) = shape_at(haystack, start)?;
+    if !is_boundary_before(haystack, start, separator)
+        || !is_boundary_after(haystack, end, separator)
+    {
+        return None;
+    }
+    let (area, group, serial) = components(&digits);
+    let tin = Tin::from_components(area, group, serial).ok();
+    let kind = match &tin {
+        Some(tin) => tin.kind(),
+        None => TinKind::of_components(area, group),
+    };
+    Some(Match {
+        start,
+        end,
+        kind,
+        tin,
+    })
+}
+
+/// Matches `XXX-XX-XXXX`, `XXX XX XXXX`, or `XXXXXXXXX` at `start`, returning
+/// the end offset, the separator used, and the digits.
+fn shape_at(haystack: &[u8], start: usize) -> Option<(usize, Option<u8>, [u8; parse::DIGITS])> {
+    let rest = &haystack[start..];
+    let separator = match rest.get(3)? {
+        b'-' => Some(b'-'),
+        b' ' => Some(b' '),
+        _ => None,
+    };
+    let len = if separator.is_some() { 11 } else { 9 };
+    let candidate = rest.get(..len)?;
+
+    let mut digits = [0u8; parse::DIGITS];
+    let mut count = 0;
+    for (i, &b) in candidate.iter().enumerate() {
+        if separator.is_some() && (i == 3 || i == 6) {
+            if Some(b) != separator {
+                return None;
+            }
+        } else if b.is_ascii_digit() {
+            digits[count] = b - b'0';
+            count += 1;
+        } else {
+            return None;
+        }
+    }
+    Some((start + len, separator, digits))
+}
+
+fn is_word(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Returns `true` if `b` between two digits would join them into one number.
+fn is_joiner(b: u8, separator: Option<u8>) -> bool {
+    matches!(b, b'-' | b'.' | b'/') || Some(b) == separator
+}
+
+fn is_boundary_before(haystack: &[u8], start: usize, separator: Option<u8>) -> bool {
+    match haystack[..start] {
+        [] => true,
+        [.., prev] if is_word(prev) => false,
+        [.., before, prev] => !(is_joiner(prev, separator) && before.is_ascii_digit()),
+        [_] => true,
+    }
+}
+
+fn is_boundary_after(haystack: &[u8], end: usize, separator: Option<u8>) -> bool {
+    match haystack[end..] {
+        [] => true,
+        [next, ..] if is_word(next) => false,
+        [next, after, ..] => !(is_joiner(next, separator) && after.is_ascii_digit()),
+        [_] => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &str) -> Vec<&str> {
+        find_iter(text).map(|m| &text[m.range()]).collect()
+    }
+
+    #[test]
+    fn finds_all_shapes() {
+        assert_eq!(
+            spans("a 123-45-6789 b 123 45 6789 c 123456789 d"),
+            ["123-45-6789", "123 45 6789", "123456789"]
+        );
+    }
+
+    #[test]
+    fn reports_spans_and_kinds() {
+        let text = "SSN: 123-45-6789; ITIN: 900-70-1234; ATIN: 900931234.";
+        let matches: Vec<_> = find_iter(text).collect();
+        assert_eq!(matches[0].range(), 5..16);
+        assert_eq!(
+            matches.iter().map(Match::kind).collect::<Vec<_>>(),
+            [TinKind::Ssn, TinKind::Itin, TinKind::Atin]
+        );
+        assert!(matches.iter().all(Match::is_valid));
+    }
+
+    #[test]
+    fn shape_only_matches() {
+        let matches: Vec<_> = find_iter("000-12-3456 and 900-10-1234").collect();
+        assert_eq!(matches.len(), 2);
+        assert!(!matches[0].is_valid());
+        assert_eq!(matches[0].kind(), TinKind::Ssn);
+        assert_eq!(matches[0].tin(), None);
+        assert!(!matches[1].is_valid());
+        assert_eq!(matches[1].kind(), TinKind::Itin);
+    }
+
+    #[test]
+    fn respects_word_boundaries() {
+        for text in [
+            "call 555-123-4567",
+            "account 1234567890123",
+            "id123456789",
+            "123456789x",
+            "1-123-45-6789",
+            "123-45-6789-0",
+            "12.123-45-6789",
+            "123-45-6789/2",
+            "800 123 45 6789",
+            "123-45 6789",
+            "_123456789",
+        ] {
+            assert_eq!(spans(text), Vec::<&str>::new(), "{text}");
+        }
+    }
+
+    #[test]
+    fn punctuation_around_matches_is_a_boundary() {
+        assert_eq!(
+            spans("(123-45-6789), \"123456789\". 123-45-6789-"),
+            ["123-45-6789", "123456789", "123-45-6789"]
+        );
+        assert_eq!(spans("№123-45-6789é"), ["123-45-6789"]);
+    }
+
+    #[test]
+    fn scans_bytes() {
+        let bytes = b"\xff\xfe123-45-6789\x00";
+        let matches: Vec<_> = find_iter(&bytes[..]).collect();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].range(), 2..13);
+    }
+
+    #[test]
+    fn find_iter_at_skips_earlier_matches() {
+        let text = "123-45-6789 234-56-7890";
+        let starts: Vec<_> = find_iter_at(text, 1).map(|m| m.start()).collect();
+        assert_eq!(starts, [12]);
+        assert_eq!(find_iter_at(text, text.len()).count(), 0);
+    }
+
+    #[test]
+    fn find_at_uses_bytes_before_pos_for_boundary() {
+        let text = b"9123456789";
+        assert_eq!(find_at(text, 1), None);
+    }
+}
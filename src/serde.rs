@@ -0,0 +1,432 @@
+//! Serde support.
+//!
+//! [`Ssn`], [`Itin`], [`Atin`], [`Ein`], and [`Tin`] serialize as their
+//! dashed string form and deserialize through [`FromStr`], so invalid numbers
+//! are rejected at the boundary. The submodules provide alternate wire forms
+//! for use with `#[serde(with = "...")]`:
+//!
+//! - [`plain`]: the undashed string, `"123456789"`
+//! - [`integer`]: a number, `123456789`
+//!
+//! Nine bare digits read back as a [`Tin`] are an SSN, ITIN, or ATIN, so
+//! `plain` writes an EIN held in a `Tin` dashed, and `integer` refuses to
+//! write it.
+//! - [`masked`]: `"XXX-XX-6789"`, serialize only
+//!
+//! # Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use tin::{Ssn, Tin};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Employee {
+//!     ssn: Ssn,
+//!     #[serde(with = "tin::serde::integer")]
+//!     payee: Tin,
+//! }
+//!
+//! let json = r#"{"ssn":"123-45-6789","payee":900701234}"#;
+//! let employee: Employee = serde_json::from_str(json).unwrap();
+//! assert!(matches!(employee.payee, Tin::Itin(_)));
+//! assert_eq!(serde_json::to_string(&employee).unwrap(), json);
+//!
+//! assert!(serde_json::from_str::<Ssn>(r#""666-12-3456""#).is_err());
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use ::serde::de::{self, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::{self, Serializer};
+use ::serde::{Deserialize, Serialize};
+
+use crate::{Atin, Ein, Itin, ParseError, Ssn, Tin, TinKind, TinKinds};
+
+impl Serialize for Ssn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ssn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+impl Serialize for Itin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Itin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+impl Serialize for Atin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Atin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+impl Serialize for Ein {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ein {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+impl Serialize for Tin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+/// Serializes as a list of kinds, `["Ssn","Ein"]`.
+impl Serialize for TinKinds {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for TinKinds {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(KindsVisitor)
+    }
+}
+
+/// Deserializes a [`TinKinds`] from a list of kind names.
+struct KindsVisitor;
+
+impl<'de> Visitor<'de> for KindsVisitor {
+    type Value = TinKinds;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a list of TIN kinds")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TinKinds, A::Error> {
+        let mut kinds = TinKinds::NONE;
+        while let Some(kind) = seq.next_element::<TinKind>()? {
+            kinds.insert(kind);
+        }
+        Ok(kinds)
+    }
+}
+
+/// Deserializes any of the crate's types from a string through [`FromStr`].
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<T: FromStr<Err = ParseError>> Visitor<'_> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a taxpayer identification number string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Ssn {}
+    impl Sealed for crate::Itin {}
+    impl Sealed for crate::Atin {}
+    impl Sealed for crate::Ein {}
+    impl Sealed for crate::Tin {}
+}
+
+/// A type [`plain`] and [`integer`] can write: [`Ssn`], [`Itin`], [`Atin`],
+/// [`Ein`], or [`Tin`].
+///
+/// The trait is sealed.
+pub trait Digits: sealed::Sealed + fmt::Display {
+    /// Returns `true` if the value reads back from its nine bare digits as
+    /// the same kind. Only an EIN held in a [`Tin`] does not.
+    fn reads_back_undashed(&self) -> bool {
+        true
+    }
+}
+
+impl Digits for Ssn {}
+impl Digits for Itin {}
+impl Digits for Atin {}
+impl Digits for Ein {}
+
+impl Digits for Tin {
+    fn reads_back_undashed(&self) -> bool {
+        !matches!(self, Tin::Ein(_))
+    }
+}
+
+/// Serializes as the undashed string, `"123456789"`.
+///
+/// An EIN held in a [`Tin`] is written dashed, `"12-3456789"`, since its
+/// undashed digits would read back as an SSN, ITIN, or ATIN. Deserialization
+/// accepts the dashed and undashed forms.
+pub mod plain {
+    use super::*;
+
+    /// Serializes `value` without dashes, unless that would change its kind.
+    pub fn serialize<T: Digits, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if value.reads_back_undashed() {
+            serializer.serialize_str(&value.to_string().replace('-', ""))
+        } else {
+            serializer.collect_str(value)
+        }
+    }
+
+    /// Deserializes from the dashed or undashed string form.
+    pub fn deserialize<'de, T: FromStr<Err = ParseError>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+/// Serializes as an integer, `123456789`.
+///
+/// Leading zeros are dropped on the wire and restored on deserialization,
+/// so `001-01-0001` round-trips through `1010001`. Serializing an EIN held
+/// in a [`Tin`] fails, since the number would read back as an SSN, ITIN, or
+/// ATIN; use the [`Ein`] type, or a string form, for EINs.
+pub mod integer {
+    use super::*;
+
+    /// Serializes `value` as a number.
+    ///
+    /// Fails for an EIN held in a [`Tin`].
+    pub fn serialize<T: Digits, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if !value.reads_back_undashed() {
+            return Err(ser::Error::custom(
+                "an EIN in a Tin cannot be written as an integer",
+            ));
+        }
+        let number = value
+            .to_string()
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .fold(0u32, |n, d| n * 10 + u32::from(d - b'0'));
+        serializer.serialize_u32(number)
+    }
+
+    /// Deserializes from a number between 0 and 999,999,999.
+    pub fn deserialize<'de, T: FromStr<Err = ParseError>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_u32(IntegerVisitor(PhantomData))
+    }
+
+    struct IntegerVisitor<T>(PhantomData<T>);
+
+    impl<T: FromStr<Err = ParseError>> Visitor<'_> for IntegerVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a nine-digit taxpayer identification number")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+            if v > 999_999_999 {
+                return Err(E::invalid_value(de::Unexpected::Unsigned(v), &self));
+            }
+            format!("{v:09}").parse().map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+            match u64::try_from(v) {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+            }
+        }
+    }
+}
+
+/// Serializes with all but the last 4 digits masked, as `Debug` does:
+/// `"XXX-XX-6789"`, or `"XX-XXX6789"` for an EIN.
+///
+/// There is no way back from the masked form, so this module only
+/// serializes. Use it with
+/// `#[serde(serialize_with = "tin::serde::masked::serialize")]`.
+pub mod masked {
+    use super::*;
+
+    /// Serializes `value` with all but the last 4 digits masked.
+    pub fn serialize<T: fmt::Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let full = value.to_string();
+        let hidden = full
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .count()
+            .saturating_sub(4);
+        let mut seen = 0;
+        let masked: String = full
+            .chars()
+            .map(|c| {
+                if !c.is_ascii_digit() {
+                    return c;
+                }
+                seen += 1;
+                if seen > hidden { c } else { 'X' }
+            })
+            .collect();
+        serializer.serialize_str(&masked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        ssn: Ssn,
+        tin: Tin,
+        #[serde(with = "plain")]
+        plain: Itin,
+        #[serde(with = "integer")]
+        integer: Ssn,
+    }
+
+    #[test]
+    fn round_trips() {
+        let json =
+            r#"{"ssn":"123-45-6789","tin":"12-3456789","plain":"900701234","integer":1010001}"#;
+        let record: Record = serde_json::from_str(json).unwrap();
+        assert_eq!(record.integer.to_string(), "001-01-0001");
+        assert!(matches!(record.tin, Tin::Ein(_)));
+        assert_eq!(serde_json::to_string(&record).unwrap(), json);
+    }
+
+    #[test]
+    fn deserialization_validates() {
+        let err = serde_json::from_str::<Ssn>(r#""000-12-3456""#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid area number: 0"));
+        assert!(serde_json::from_str::<Itin>(r#""123-45-6789""#).is_err());
+        assert!(serde_json::from_str::<Atin>("123456789").is_err());
+        assert_eq!(
+            serde_json::from_str::<Atin>(r#""900931234""#)
+                .unwrap()
+                .to_string(),
+            "900-93-1234"
+        );
+        assert!(serde_json::from_str::<Ein>(r#""07-1234567""#).is_err());
+    }
+
+    #[test]
+    fn eins_in_a_tin_keep_their_kind() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Plain(#[serde(with = "plain")] Tin);
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Integer(#[serde(with = "integer")] Tin);
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct IntegerEin(#[serde(with = "integer")] Ein);
+
+        let tin: Tin = "66-6123456".parse().unwrap();
+        let json = serde_json::to_string(&Plain(tin.clone())).unwrap();
+        assert_eq!(json, r#""66-6123456""#);
+        assert_eq!(serde_json::from_str::<Plain>(&json).unwrap().0, tin);
+
+        let err = serde_json::to_string(&Integer(tin)).unwrap_err();
+        assert!(err.to_string().contains("EIN"), "{err}");
+
+        let ein: Ein = "66-6123456".parse().unwrap();
+        let json = serde_json::to_string(&IntegerEin(ein.clone())).unwrap();
+        assert_eq!(json, "666123456");
+        assert_eq!(serde_json::from_str::<IntegerEin>(&json).unwrap().0, ein);
+    }
+
+    #[test]
+    fn integer_bounds() {
+        #[derive(Debug, Deserialize)]
+        struct Wrapper(#[serde(with = "integer")] Tin);
+
+        assert!(serde_json::from_str::<Wrapper>("1000000000").is_err());
+        assert!(serde_json::from_str::<Wrapper>("-1").is_err());
+        assert!(serde_json::from_str::<Wrapper>("\"123456789\"").is_err());
+        assert!(matches!(
+            serde_json::from_str::<Wrapper>("900931234").unwrap().0,
+            Tin::Atin(_)
+        ));
+    }
+
+    #[test]
+    fn kinds_are_a_list() {
+        let kinds = TinKinds::SSN | TinKinds::EIN;
+        assert_eq!(serde_json::to_string(&kinds).unwrap(), r#"["Ssn","Ein"]"#);
+        assert_eq!(
+            serde_json::from_str::<TinKinds>(r#"["Ein","Ssn","Ein"]"#).unwrap(),
+            kinds
+        );
+        assert_eq!(
+            serde_json::from_str::<TinKinds>("[]").unwrap(),
+            TinKinds::NONE
+        );
+        assert!(serde_json::from_str::<TinKinds>("255").is_err());
+        assert!(serde_json::from_str::<TinKinds>(r#"["Ptin"]"#).is_err());
+    }
+
+    #[test]
+    fn masked_serialization() {
+        #[derive(Serialize)]
+        struct Outbound {
+            #[serde(serialize_with = "masked::serialize")]
+            ssn: Ssn,
+            #[serde(serialize_with = "masked::serialize")]
+            tin: Tin,
+        }
+
+        let outbound = Outbound {
+            ssn: "123-45-6789".parse().unwrap(),
+            tin: "12-3456789".parse().unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_string(&outbound).unwrap(),
+            r#"{"ssn":"XXX-XX-6789","tin":"XX-XXX6789"}"#
+        );
+    }
+
+    #[test]
+    fn parse_error_round_trips() {
+        let err = "900-10-1234".parse::<Tin>().unwrap_err();
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(json, r#"{"InvalidGroup":10}"#);
+        assert_eq!(serde_json::from_str::<ParseError>(&json).unwrap(), err);
+    }
+}
@@ -1,9 +1,13 @@
 //! U.S. Social Security Number (SSN) validation.
 
 use core::fmt;
+use core::ops::RangeInclusive;
 use core::str::FromStr;
 
-use crate::{ParseError, parse_components};
+use crate::{
+    Masked, ParseError, ParseOptions, TaxId, TaxIdRanges, TinKind, parse_components,
+    parse_components_with,
+};
 
 /// A validated U.S. Social Security Number.
 ///
@@ -13,7 +17,8 @@ use crate::{ParseError, parse_components};
 /// - Area number (first 3 digits) must be 001–665 or 667–899
 /// - Group number (middle 2 digits) must be 01–99
 /// - Serial number (last 4 digits) must be 0001–9999
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
+#[cfg_attr(not(feature = "hardened"), derive(PartialEq, Eq, Hash))]
 pub struct Ssn {
     area: u16,
     group: u8,
@@ -31,6 +36,42 @@ impl Ssn {
         })
     }
 
+    /// Returns the area number (first 3 digits).
+    pub fn area(&self) -> u16 {
+        self.area
+    }
+
+    /// Returns the group number (middle 2 digits).
+    pub fn group(&self) -> u8 {
+        self.group
+    }
+
+    /// Returns the serial number (last 4 digits).
+    pub fn serial(&self) -> u16 {
+        self.serial
+    }
+
+    /// Parses an SSN, accepting the spellings allowed by `options`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        let (area, group, serial) = parse_components_with(s, options)?;
+        Self::new(area, group, serial)
+    }
+
+    /// Returns a wrapper whose `Display` masks all but the last 4 digits.
+    pub fn masked(&self) -> Masked<Self> {
+        Masked::new(self.clone())
+    }
+}
+
+impl TaxIdRanges for Ssn {
+    const AREAS: &'static [RangeInclusive<u16>] = &[1..=665, 667..=899];
+    const GROUPS: &'static [RangeInclusive<u8>] = &[1..=99];
+    const SERIALS: RangeInclusive<u16> = 1..=9999;
+
+    fn new(area: u16, group: u8, serial: u16) -> Result<Self, ParseError> {
+        Ssn::new(area, group, serial)
+    }
+
     fn validate(area: u16, group: u8, serial: u16) -> Result<(), ParseError> {
         if area == 0 || area == 666 || area > 899 {
             return Err(ParseError::InvalidArea(area));
@@ -43,20 +84,19 @@ impl Ssn {
         }
         Ok(())
     }
+}
 
-    /// Returns the area number (first 3 digits).
-    pub fn area(&self) -> u16 {
-        self.area
+impl TaxId for Ssn {
+    fn components(&self) -> (u16, u8, u16) {
+        (self.area, self.group, self.serial)
     }
 
-    /// Returns the group number (middle 2 digits).
-    pub fn group(&self) -> u8 {
-        self.group
+    fn kind(&self) -> TinKind {
+        TinKind::Ssn
     }
 
-    /// Returns the serial number (last 4 digits).
-    pub fn serial(&self) -> u16 {
-        self.serial
+    fn fmt_masked(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "XXX-XX-{:04}", self.serial)
     }
 }
 
@@ -77,10 +117,65 @@ impl fmt::Display for Ssn {
 
 impl fmt::Debug for Ssn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Ssn(XXX-XX-{:04})", self.serial)
+        f.write_str("Ssn(")?;
+        self.fmt_masked(f)?;
+        f.write_str(")")
     }
 }
 
+#[cfg(feature = "hardened")]
+impl Ssn {
+    /// Overwrites the components with zeros.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.area.zeroize();
+        self.group.zeroize();
+        self.serial.zeroize();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl subtle::ConstantTimeEq for Ssn {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.area.ct_eq(&other.area)
+            & self.group.ct_eq(&other.group)
+            & self.serial.ct_eq(&other.serial)
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl PartialEq for Ssn {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.ct_eq(other).into()
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Eq for Ssn {}
+
+#[cfg(feature = "hardened")]
+impl core::hash::Hash for Ssn {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // A single fixed-width write, whatever the components.
+        state.write_u32(
+            u32::from(self.area) * 1_000_000
+                + u32::from(self.group) * 10_000
+                + u32::from(self.serial),
+        );
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl Drop for Ssn {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "hardened")]
+impl zeroize::ZeroizeOnDrop for Ssn {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +289,23 @@ mod tests {
         assert_eq!(format!("{ssn:?}"), "Ssn(XXX-XX-6789)");
     }
 
+    #[test]
+    fn parse_with_options() {
+        let options = ParseOptions::lenient();
+        for input in ["123 45 6789", "123.45.6789", " 123-45-6789 ", "123-45.6789"] {
+            let ssn = Ssn::parse_with(input, &options).unwrap();
+            assert_eq!(ssn.to_string(), "123-45-6789");
+        }
+        assert!(matches!(
+            Ssn::parse_with("666 45 6789", &options),
+            Err(ParseError::InvalidArea(666))
+        ));
+        assert!(matches!(
+            Ssn::parse_with("123 45 6789", &ParseOptions::new()),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn invalid_format() {
         assert!(matches!(
@@ -201,4 +313,21 @@ mod tests {
             Err(ParseError::InvalidFormat(_))
         ));
     }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn hardened_eq_hash_and_wipe() {
+        use std::hash::BuildHasher;
+
+        let mut ssn: Ssn = "123-45-6789".parse().unwrap();
+        let same: Ssn = "123-45-6789".parse().unwrap();
+        let other: Ssn = "123-45-6780".parse().unwrap();
+        assert_eq!(ssn, same);
+        assert_ne!(ssn, other);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&ssn), state.hash_one(&same));
+
+        ssn.wipe();
+        assert_eq!((ssn.area(), ssn.group(), ssn.serial()), (0, 0, 0));
+    }
 }
@@ -0,0 +1,218 @@
+//! The trait shared by the `XXX-XX-XXXX` identifiers.
+
+use core::fmt;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+
+use crate::{ParseError, TinKind, convert};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Ssn {}
+    impl Sealed for crate::Itin {}
+    impl Sealed for crate::Atin {}
+    impl Sealed for crate::Tin {}
+}
+
+/// An identifier read as an area, group, and serial number: [`Ssn`],
+/// [`Itin`], [`Atin`], or [`Tin`].
+///
+/// The trait is sealed; it exists so code can be generic over these types,
+/// not to add new ones. `Masked<T>` displays masked for any `T: TaxId`.
+///
+/// A [`Tin`] delegates to the value it holds, and splits an EIN's nine
+/// digits 3-2-4. Those components need not fall in any range, so the range
+/// constants and constructors are on [`TaxIdRanges`], which `Tin` does not
+/// implement.
+///
+/// # Example
+///
+/// ```
+/// use tin::{Itin, Masked, Ssn, TaxId, Tin};
+///
+/// fn describe<T: TaxId>(s: &str) -> String {
+///     match s.parse::<T>() {
+///         Ok(id) => format!("{} {}", id.kind(), Masked::new(id)),
+///         Err(err) => err.to_string(),
+///     }
+/// }
+///
+/// assert_eq!(describe::<Ssn>("123-45-6789"), "SSN XXX-XX-6789");
+/// assert_eq!(describe::<Itin>("123-45-6789"), "invalid area number: 123");
+/// assert_eq!(describe::<Tin>("12-3456789"), "EIN XX-XXX6789");
+/// ```
+///
+/// [`Ssn`]: crate::Ssn
+/// [`Itin`]: crate::Itin
+/// [`Atin`]: crate::Atin
+/// [`Tin`]: crate::Tin
+pub trait TaxId:
+    sealed::Sealed + Clone + fmt::Display + fmt::Debug + FromStr<Err = ParseError>
+{
+    /// Returns `(area, group, serial)`.
+    fn components(&self) -> (u16, u8, u16);
+
+    /// Returns the kind of the value.
+    fn kind(&self) -> TinKind;
+
+    /// Writes the number with all but the last 4 digits masked, as `Debug`
+    /// does.
+    fn fmt_masked(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Returns the area number (first 3 digits).
+    fn area(&self) -> u16 {
+        self.components().0
+    }
+
+    /// Returns the group number (middle 2 digits).
+    fn group(&self) -> u8 {
+        self.components().1
+    }
+
+    /// Returns the serial number (last 4 digits).
+    fn serial(&self) -> u16 {
+        self.components().2
+    }
+
+    /// Returns the nine digits, each 0–9, most significant first.
+    fn to_digits(&self) -> [u8; 9] {
+        convert::digits(self.components())
+    }
+}
+
+/// A [`TaxId`] with fixed ranges for each component: [`Ssn`], [`Itin`], or
+/// [`Atin`].
+///
+/// Every value's components fall in the ranges, and `new` on them rebuilds
+/// the same value. The trait is sealed.
+///
+/// # Example
+///
+/// ```
+/// use tin::{Itin, TaxIdRanges};
+///
+/// assert!(Itin::GROUPS.iter().any(|groups| groups.contains(&70)));
+/// assert!(<Itin as TaxIdRanges>::validate(900, 70, 1234).is_ok());
+/// ```
+///
+/// [`Ssn`]: crate::Ssn
+/// [`Itin`]: crate::Itin
+/// [`Atin`]: crate::Atin
+pub trait TaxIdRanges: TaxId {
+    /// The area numbers (first 3 digits) the type accepts.
+    const AREAS: &'static [RangeInclusive<u16>];
+    /// The group numbers (middle 2 digits) the type accepts.
+    const GROUPS: &'static [RangeInclusive<u8>];
+    /// The serial numbers (last 4 digits) the type accepts.
+    const SERIALS: RangeInclusive<u16>;
+
+    /// Creates a value from its components.
+    fn new(area: u16, group: u8, serial: u16) -> Result<Self, ParseError>;
+
+    /// Checks components against the type's rules without creating a value.
+    fn validate(area: u16, group: u8, serial: u16) -> Result<(), ParseError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Atin, Itin, Masked, Ssn, Tin};
+
+    /// Returns an error for the first component outside the ranges.
+    fn check_ranges<T: TaxIdRanges>(
+        (area, group, serial): (u16, u8, u16),
+    ) -> Result<(), ParseError> {
+        if !T::AREAS.iter().any(|range| range.contains(&area)) {
+            return Err(ParseError::InvalidArea(area));
+        }
+        if !T::GROUPS.iter().any(|range| range.contains(&group)) {
+            return Err(ParseError::InvalidGroup(group));
+        }
+        if !T::SERIALS.contains(&serial) {
+            return Err(ParseError::InvalidSerial(serial));
+        }
+        Ok(())
+    }
+
+    /// Checks that `validate` agrees with the range constants on every area
+    /// and group, and at the serial boundaries.
+    fn validate_matches_ranges<T: TaxIdRanges>() {
+        for area in 0..=1000 {
+            for group in 0..=100 {
+                for serial in [0, 1, 9999, 10_000] {
+                    let expected = check_ranges::<T>((area, group, serial));
+                    assert_eq!(T::validate(area, group, serial), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn constants_describe_validation() {
+        validate_matches_ranges::<Ssn>();
+        validate_matches_ranges::<Itin>();
+        validate_matches_ranges::<Atin>();
+    }
+
+    #[test]
+    fn generic_access_matches_inherent_methods() {
+        fn roundtrip<T: TaxIdRanges>(s: &str) -> (u16, u8, u16, TinKind, String) {
+            let id: T = s.parse().unwrap();
+            let (area, group, serial) = id.components();
+            let rebuilt = T::new(area, group, serial).unwrap();
+            assert_eq!(rebuilt.to_string(), id.to_string());
+            (
+                id.area(),
+                id.group(),
+                id.serial(),
+                id.kind(),
+                Masked::new(id).to_string(),
+            )
+        }
+
+        assert_eq!(
+            roundtrip::<Ssn>("123-45-6789"),
+            (123, 45, 6789, TinKind::Ssn, "XXX-XX-6789".to_owned())
+        );
+        assert_eq!(
+            roundtrip::<Atin>("900-93-0001"),
+            (900, 93, 1, TinKind::Atin, "XXX-XX-0001".to_owned())
+        );
+        assert_eq!(
+            roundtrip::<Itin>("900-70-1234"),
+            (900, 70, 1234, TinKind::Itin, "XXX-XX-1234".to_owned())
+        );
+    }
+
+    #[test]
+    fn tin_delegates_to_the_value_it_holds() {
+        fn read<T: TaxId>(id: &T) -> (u16, u8, u16, TinKind, String) {
+            (
+                id.area(),
+                id.group(),
+                id.serial(),
+                id.kind(),
+                Masked::new(id.clone()).to_string(),
+            )
+        }
+
+        for s in ["123-45-6789", "900-70-1234", "900-93-0001"] {
+            let tin: Tin = s.parse().unwrap();
+            let inner = match &tin {
+                Tin::Ssn(v) => read(v),
+                Tin::Itin(v) => read(v),
+                Tin::Atin(v) => read(v),
+                Tin::Ein(_) => unreachable!(),
+            };
+            assert_eq!(read(&tin), inner);
+        }
+
+        let ein: Tin = "66-6123456".parse().unwrap();
+        assert_eq!(
+            read(&ein),
+            (666, 12, 3456, TinKind::Ein, "XX-XXX3456".to_owned())
+        );
+        assert_eq!(TaxId::to_digits(&ein), [6, 6, 6, 1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Sets of TIN kinds.
+
+use core::fmt;
+use core::ops::{BitAnd, BitOr, BitOrAssign, Sub};
+
+use crate::TinKind;
+
+/// A set of [`TinKind`]s, such as the kinds a form accepts.
+///
+/// Combine the constants with `|`, or collect kinds into a set. With the
+/// `serde` feature the set serializes as a list of kinds, `["Ssn","Itin"]`.
+///
+/// # Example
+///
+/// ```
+/// use tin::{TinKind, TinKinds};
+///
+/// let allowed = TinKinds::SSN | TinKinds::ITIN;
+/// assert!(allowed.contains(TinKind::Itin));
+/// assert!(!allowed.contains(TinKind::Atin));
+/// assert_eq!(allowed.to_string(), "SSN or ITIN");
+/// assert_eq!(TinKinds::INDIVIDUAL - TinKinds::ATIN, allowed);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TinKinds(u8);
+
+/// Every kind, in the order the set lists them.
+const KINDS: [TinKind; 4] = [TinKind::Ssn, TinKind::Itin, TinKind::Atin, TinKind::Ein];
+
+impl TinKinds {
+    /// The empty set.
+    pub const NONE: Self = Self(0);
+    /// Social Security Numbers.
+    pub const SSN: Self = Self::of(TinKind::Ssn);
+    /// Individual Taxpayer Identification Numbers.
+    pub const ITIN: Self = Self::of(TinKind::Itin);
+    /// Adoption Taxpayer Identification Numbers.
+    pub const ATIN: Self = Self::of(TinKind::Atin);
+    /// Employer Identification Numbers.
+    pub const EIN: Self = Self::of(TinKind::Ein);
+    /// The kinds issued to people: SSN, ITIN, and ATIN.
+    pub const INDIVIDUAL: Self = Self(Self::SSN.0 | Self::ITIN.0 | Self::ATIN.0);
+    /// Every kind.
+    pub const ALL: Self = Self(Self::INDIVIDUAL.0 | Self::EIN.0);
+
+    /// Returns the set holding only `kind`.
+    pub const fn of(kind: TinKind) -> Self {
+        Self(1 << kind as u8)
+    }
+
+    /// Returns `true` if the set holds `kind`.
+    pub const fn contains(self, kind: TinKind) -> bool {
+        self.0 & Self::of(kind).0 != 0
+    }
+
+    /// Returns `true` if the set holds no kinds.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of kinds in the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Adds `kind` to the set.
+    pub fn insert(&mut self, kind: TinKind) {
+        self.0 |= Self::of(kind).0;
+    }
+
+    /// Removes `kind` from the set.
+    pub fn remove(&mut self, kind: TinKind) {
+        self.0 &= !Self::of(kind).0;
+    }
+
+    /// Iterates over the kinds in the set, in [`TinKind`] order.
+    pub fn iter(self) -> impl Iterator<Item = TinKind> {
+        KINDS.into_iter().filter(move |&kind| self.contains(kind))
+    }
+}
+
+impl From<TinKind> for TinKinds {
+    fn from(kind: TinKind) -> Self {
+        Self::of(kind)
+    }
+}
+
+impl FromIterator<TinKind> for TinKinds {
+    fn from_iter<I: IntoIterator<Item = TinKind>>(iter: I) -> Self {
+        let mut kinds = Self::NONE;
+        for kind in iter {
+            kinds.insert(kind);
+        }
+        kinds
+    }
+}
+
+impl BitOr for TinKinds {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for TinKinds {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for TinKinds {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Sub for TinKinds {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+/// Lists the kinds as prose: `SSN`, `SSN or ITIN`, `SSN, ITIN, or ATIN`.
+impl fmt::Display for TinKinds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.len();
+        if len == 0 {
+            return f.write_str("no kind");
+        }
+        for (i, kind) in self.iter().enumerate() {
+            match (i, len) {
+                (0, _) => {}
+                (1, 2) => f.write_str(" or ")?,
+                (i, len) if i + 1 == len => f.write_str(", or ")?,
+                _ => f.write_str(", ")?,
+            }
+            write!(f, "{kind}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TinKinds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let mut kinds = TinKinds::NONE;
+        assert!(kinds.is_empty());
+        kinds.insert(TinKind::Ein);
+        kinds |= TinKinds::SSN;
+        assert_eq!(kinds.len(), 2);
+        assert_eq!(
+            kinds.iter().collect::<Vec<_>>(),
+            [TinKind::Ssn, TinKind::Ein]
+        );
+        assert_eq!(kinds & TinKinds::INDIVIDUAL, TinKinds::SSN);
+        kinds.remove(TinKind::Ssn);
+        assert_eq!(kinds, TinKinds::EIN);
+        assert_eq!(KINDS.into_iter().collect::<TinKinds>(), TinKinds::ALL);
+    }
+
+    #[test]
+    fn display_reads_as_prose() {
+        assert_eq!(TinKinds::NONE.to_string(), "no kind");
+        assert_eq!(TinKinds::EIN.to_string(), "EIN");
+        assert_eq!((TinKinds::SSN | TinKinds::EIN).to_string(), "SSN or EIN");
+        assert_eq!(TinKinds::ALL.to_string(), "SSN, ITIN, ATIN, or EIN");
+        assert_eq!(
+            format!("{:?}", TinKinds::ITIN | TinKinds::ATIN),
+            "{Itin, Atin}"
+        );
+    }
+}